@@ -0,0 +1,404 @@
+use std::{ffi::OsString, path::PathBuf};
+use super::*;
+
+// options given on the command line
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Options {
+	// folder containing the .rce files, see file::resolve_location() if omitted
+	pub data: Option<PathBuf>,
+	// pretend today is this date, see event::Context
+	pub today: Option<date::Fixed>,
+	// exit with 1 if nothing happens within this many days
+	pub alert_within: Option<u32>,
+	// expand the two-digit years of the dates given on the command line, see date::DateParser
+	pub year_pivot: Option<i32>,
+	// suggest bridge days for holidays on Tuesday or Thursday
+	pub bridge_days: bool,
+	// print the number of events per month instead of the next events
+	pub histogram: bool,
+	// merge the events of the same kind on the same date
+	pub merge: bool,
+	// give one event per day of the spanning holidays instead of a summary
+	pub expand: bool,
+	// print every upcoming event by date instead of the next events per kind
+	pub timeline: bool,
+	// only keep the events of this kind
+	pub kind: Option<event::EventKind>,
+	// only keep the events carrying this tag
+	pub tag: Option<String>,
+	// print at most this many events per kind, 0 for all of them
+	pub limit: usize,
+	// only keep the events happening in [since, until]
+	pub since: Option<date::Fixed>,
+	pub until: Option<date::Fixed>,
+	// list every event happening within this many days instead of the next events
+	pub next_days: Option<u32>,
+	// also print the events that happened within this many days
+	pub ago: Option<u32>,
+	// list every event happening in [from, to] instead of the next events
+	pub from: Option<date::Fixed>,
+	pub to: Option<date::Fixed>,
+	// print the events added and removed between two folders
+	pub diff: Option<(PathBuf, PathBuf)>,
+	// also print the last past events of each kind
+	pub show_overdue: bool,
+	// also print when the events of each kind last happened, recurring ones included
+	pub past: bool,
+	// move past one-off events to their next yearly occurrence
+	pub force_recurring: bool,
+	// append the personal notes to the descriptions
+	pub notes: bool,
+	// skip the files that panic while parsing
+	pub robust: bool,
+	// stop at the first line failing to parse instead of skipping it
+	pub strict: bool,
+	// create the data folder with an example file
+	pub init: bool,
+	// append the zodiac sign to the birthdays
+	pub zodiac: bool,
+	// start with the soonest holiday, whatever its kind
+	pub next_holiday: bool,
+	// only a '#' starting a line introduces a comment
+	pub comment_at_line_start: bool,
+	// start with how far through the year today is
+	pub year_progress: bool,
+	// print the dates less than a week away as words
+	pub relative: bool,
+	// rewrite the .rce files in canonical form, keeping a .bak copy
+	pub normalize: bool,
+	// only parse the .rce files, reporting every error
+	pub check: bool,
+	// language of the printed messages and dates, the dates keeping their plain format if None
+	pub locale: Option<locale::Locale>,
+	// print the next events as JSON, without colors nor messages
+	pub json: bool,
+	// print only the single soonest event, whatever its kind
+	pub top: bool,
+	// print every event as tab-separated values, without colors nor messages
+	pub tsv: bool,
+	// list every event happening in this year instead of the next events
+	pub year: Option<i32>,
+	// what to do with 29/02 in a year that is not leap, see event::Context
+	pub leap_day: date::LeapDayPolicy,
+	// redraw the next events every time a .rce file changes
+	pub watch: bool,
+	// print without colors, see color_enabled()
+	pub no_color: bool,
+	// print what was read from the .rce files to stderr
+	pub verbose: bool,
+}
+
+// environment variable disabling the colors when set and not empty, see https://no-color.org
+pub const NO_COLOR_VAR: &str = "NO_COLOR";
+
+// return false if the colors are disabled by --no-color or by <var>, the value of NO_COLOR
+pub fn color_enabled(no_color: bool, var: Option<OsString>) -> bool {
+	!no_color && var.is_none_or(|var| var.is_empty())
+}
+
+// return the value following an option
+fn value<I: Iterator<Item = String>>(args: &mut I) -> Result<String> {
+	let Some(value) = args.next() else {
+		return Err(Error::Parse{ what: "missing option value" });
+	};
+	Ok(value)
+}
+
+impl Options {
+
+	// parse the arguments, program name excluded
+	pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Self> {
+		let mut options = Options::default();
+		// the dates are parsed once every option is known, --year-pivot possibly coming after them
+		let (mut since, mut until, mut today, mut from, mut to) = (None, None, None, None, None);
+		while let Some(arg) = args.next() {
+			match arg.as_str() {
+				"--alert-within" => {
+					let Ok(days) = value(&mut args)?.trim().parse::<u32>() else {
+						return Err(Error::Parse{ what: "failed to parse --alert-within" });
+					};
+					options.alert_within = Some(days);
+				},
+				"--year-pivot" => {
+					let Ok(pivot) = value(&mut args)?.trim().parse::<i32>() else {
+						return Err(Error::Parse{ what: "failed to parse --year-pivot" });
+					};
+					if !(0..=100).contains(&pivot) {
+						return Err(Error::OutOfRange{ what: "--year-pivot must be in 0..=100" });
+					}
+					options.year_pivot = Some(pivot);
+				},
+				"--next" => {
+					let Ok(days) = value(&mut args)?.trim().parse::<u32>() else {
+						return Err(Error::Parse{ what: "failed to parse --next" });
+					};
+					options.next_days = Some(days);
+				},
+				"--ago" => {
+					let Ok(days) = value(&mut args)?.trim().parse::<u32>() else {
+						return Err(Error::Parse{ what: "failed to parse --ago" });
+					};
+					options.ago = Some(days);
+				},
+				"--kind" => options.kind = Some(event::EventKind::try_from(value(&mut args)?.as_str())?),
+				"--tag" => options.tag = Some(value(&mut args)?),
+				"--limit" => {
+					let Ok(limit) = value(&mut args)?.trim().parse::<usize>() else {
+						return Err(Error::Parse{ what: "failed to parse --limit" });
+					};
+					options.limit = limit;
+				},
+				"--bridge-days" => options.bridge_days = true,
+				"--histogram" => options.histogram = true,
+				"--timeline" => options.timeline = true,
+				"--expand" => options.expand = true,
+				"--merge" => options.merge = true,
+				"--since" => since = Some(value(&mut args)?),
+				"--until" => until = Some(value(&mut args)?),
+				"--date" => today = Some(value(&mut args)?),
+				"--from" => from = Some(value(&mut args)?),
+				"--to" => to = Some(value(&mut args)?),
+				"--show-overdue" => options.show_overdue = true,
+				"--past" => options.past = true,
+				"--notes" => options.notes = true,
+				"--robust" => options.robust = true,
+				"--strict" => options.strict = true,
+				"--init" => options.init = true,
+				"--zodiac" => options.zodiac = true,
+				"--next-holiday" => options.next_holiday = true,
+				"--comment-at-line-start" => options.comment_at_line_start = true,
+				"--year-progress" => options.year_progress = true,
+				"--relative" | "--compact-dates" => options.relative = true,
+				"--force-recurring" => options.force_recurring = true,
+				"--normalize" => options.normalize = true,
+				"--check" => options.check = true,
+				"--json" => options.json = true,
+				"--tsv" => options.tsv = true,
+				"--top" => options.top = true,
+				"--watch" => options.watch = true,
+				"--no-color" => options.no_color = true,
+				"--verbose" => options.verbose = true,
+				"--year" => {
+					let Ok(year) = value(&mut args)?.trim().parse::<i32>() else {
+						return Err(Error::Parse{ what: "failed to parse --year" });
+					};
+					options.year = Some(year);
+				},
+				"--leap-day" => options.leap_day = date::LeapDayPolicy::try_from(value(&mut args)?.as_str())?,
+				"--locale" => options.locale = Some(locale::Locale::try_from(value(&mut args)?.as_str())?),
+				"--diff" => {
+					let a = PathBuf::from(value(&mut args)?);
+					let b = PathBuf::from(value(&mut args)?);
+					options.diff = Some((a, b));
+				},
+				_ if arg.starts_with("--") => {
+					return Err(Error::Parse{ what: "unknown option" });
+				},
+				_ => {
+					if options.data.is_some() {
+						return Err(Error::Parse{ what: "extra argument found" });
+					}
+					options.data = Some(PathBuf::from(arg));
+				},
+			}
+		}
+		let parser = date::DateParser::new().year_pivot(options.year_pivot);
+		let parse = |date: Option<String>| date.map(|date| parser.parse_fixed(&date)).transpose();
+		options.since = parse(since)?;
+		options.until = parse(until)?;
+		options.today = parse(today)?;
+		options.from = parse(from)?;
+		options.to = parse(to)?;
+		if let (Some(since), Some(until)) = (options.since, options.until) {
+			if since > until {
+				return Err(Error::OutOfRange{ what: "--since is after --until" });
+			}
+		}
+		if options.from.is_some() != options.to.is_some() {
+			return Err(Error::Parse{ what: "--from and --to must be given together" });
+		}
+		Ok(options)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn parse(args: &[&str]) -> Result<Options> {
+		Options::parse(args.iter().map(|arg| String::from(*arg)))
+	}
+
+	#[test]
+	fn parse_empty() {
+		assert_eq!(
+			parse(&[]).unwrap(),
+			Options::default()
+		);
+	}
+	#[test]
+	fn parse_alert_within() {
+		let options = parse(&["data", "--alert-within", "3"]).unwrap();
+		assert_eq!(
+			options.data,
+			Some(PathBuf::from("data"))
+		);
+		assert_eq!(
+			options.alert_within,
+			Some(3)
+		);
+	}
+	#[test]
+	fn parse_since_until() {
+		let options = parse(&["--since", "1,1,2030", "--until", "31,12,2030"]).unwrap();
+		assert_eq!(
+			options.since,
+			Some(date::Fixed::new(1,1,2030))
+		);
+		assert_eq!(
+			options.until,
+			Some(date::Fixed::new(31,12,2030))
+		);
+		assert!(
+			parse(&["--since", "1,1,2031", "--until", "31,12,2030"])
+			.is_err()
+		);
+	}
+	#[test]
+	fn parse_year_pivot_dates() {
+		let options = parse(&["--date", "1,1,30", "--year-pivot", "50", "--since", "1,1,99"]).unwrap();
+		assert_eq!(
+			(options.today, options.since),
+			(Some(date::Fixed::new(1,1,2030)), Some(date::Fixed::new(1,1,1999)))
+		);
+		assert_eq!(
+			parse(&["--date", "1,1,30"]).unwrap().today,
+			Some(date::Fixed::new(1,1,30))
+		);
+	}
+	#[test]
+	fn parse_from_to() {
+		let options = parse(&["--from", "1,1,2030", "--to", "31,1,2030"]).unwrap();
+		assert_eq!(
+			(options.from, options.to),
+			(Some(date::Fixed::new(1,1,2030)), Some(date::Fixed::new(31,1,2030)))
+		);
+		assert!(
+			parse(&["--from", "1,1,2030"])
+			.is_err()
+		);
+	}
+	#[test]
+	fn parse_date() {
+		assert_eq!(
+			parse(&["--date", "24,12,2030"]).unwrap().today,
+			Some(date::Fixed::new(24,12,2030))
+		);
+		assert!(
+			parse(&["--date", "24,12"])
+			.is_err()
+		);
+	}
+	#[test]
+	fn color_disabled() {
+		assert!(color_enabled(false, None));
+		assert!(color_enabled(false, Some(OsString::new())));
+		assert!(!color_enabled(false, Some(OsString::from("1"))));
+		assert!(!color_enabled(true, None));
+		assert!(parse(&["--no-color"]).unwrap().no_color);
+	}
+	#[test]
+	fn parse_next() {
+		assert_eq!(
+			parse(&["--next", "7"]).unwrap().next_days,
+			Some(7)
+		);
+		assert!(
+			parse(&["--next", "week"])
+			.is_err()
+		);
+	}
+	#[test]
+	fn parse_ago() {
+		assert_eq!(
+			parse(&["--ago", "3"]).unwrap().ago,
+			Some(3)
+		);
+		assert!(
+			parse(&["--ago", "-1"])
+			.is_err()
+		);
+	}
+	#[test]
+	fn parse_locale() {
+		assert_eq!(
+			parse(&["--locale", "fr"]).unwrap().locale,
+			Some(locale::Locale::French)
+		);
+		assert!(
+			parse(&["--locale", "xx"])
+			.is_err()
+		);
+	}
+	#[test]
+	fn parse_year() {
+		let options = parse(&["--year", "2023", "--leap-day", "skip"]).unwrap();
+		assert_eq!(
+			options.year,
+			Some(2023)
+		);
+		assert_eq!(
+			options.leap_day,
+			date::LeapDayPolicy::Skip
+		);
+		assert_eq!(
+			parse(&["--leap-day", "mar1"]).unwrap().leap_day,
+			date::LeapDayPolicy::Mar1
+		);
+		assert!(
+			parse(&["--leap-day", "never"])
+			.is_err()
+		);
+	}
+	#[test]
+	fn parse_kind() {
+		assert_eq!(
+			parse(&["--kind", "birthday"]).unwrap().kind,
+			Some(event::EventKind::Birthday)
+		);
+		assert!(
+			parse(&["--kind", "birthdays"])
+			.is_err()
+		);
+	}
+	#[test]
+	fn parse_tag() {
+		assert_eq!(
+			parse(&["--tag", "work"]).unwrap().tag,
+			Some(String::from("work"))
+		);
+		assert!(
+			parse(&["--tag"])
+			.is_err()
+		);
+	}
+	#[test]
+	fn parse_invalid() {
+		assert!(
+			parse(&["--alert-within"])
+			.is_err()
+		);
+		assert!(
+			parse(&["--alert-within", "-1"])
+			.is_err()
+		);
+		assert!(
+			parse(&["--unknown"])
+			.is_err()
+		);
+		assert!(
+			parse(&["data", "other"])
+			.is_err()
+		);
+	}
+} // mod test
@@ -4,29 +4,131 @@ use super::{Result, Error};
 
 type Day = u32;
 type Month = u32;
+// astronomical year numbering on the proleptic Gregorian calendar:
+// year 0 exists and is 1 BC (a leap year), year -1 is 2 BC, and so on
 type Year = i32;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Recurring {
 	month: Month,
 	day: Day,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Fixed {
 	year: Year,
 	date: Recurring,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Weekday {
+	Monday,
+	Tuesday,
+	Wednesday,
+	Thursday,
+	Friday,
+	Saturday,
+	Sunday,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Zodiac {
+	Aries,
+	Taurus,
+	Gemini,
+	Cancer,
+	Leo,
+	Virgo,
+	Libra,
+	Scorpio,
+	Sagittarius,
+	Capricorn,
+	Aquarius,
+	Pisces,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AnyDate {
 	Recurring(Recurring),
 	Fixed(Fixed),
 }
 
+// comes back every week on the same day
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Weekly {
+	pub weekday: Weekday,
+}
+
+// comes back on a date its day & month alone cannot tell
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Rule {
+	// moves with Easter Sunday, e.g. -2 for Good Friday
+	Easter { offset_days: i32 },
+	// the <nth> <weekday> of <month>, see nth_weekday()
+	NthWeekday { month: Month, weekday: Weekday, nth: i32 },
+	// the day & month of <since>, every <years> years from then on (0 being read as 1)
+	EveryYears { since: Fixed, years: u32 },
+}
+
+// what to do with 29/02 in a year that is not leap
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum LeapDayPolicy {
+	// move it to 28/02
+	#[default]
+	Feb28,
+	// drop it, the date does not happen that year
+	Skip,
+	// move it to 01/03
+	Mar1,
+}
+
+impl TryFrom<&str> for LeapDayPolicy {
+	type Error = Error;
+	fn try_from(value: &str) -> Result<Self> {
+		match value.trim() {
+			"feb28" => Ok(LeapDayPolicy::Feb28),
+			"skip" => Ok(LeapDayPolicy::Skip),
+			"mar1" => Ok(LeapDayPolicy::Mar1),
+			_ => Err(Error::Parse{ what: "unknown leap day policy" }),
+		}
+	}
+}
+
+// expand a two-digit year according to the pivot, if enabled
+fn expand_year(slot: &str, year: Year, pivot: Option<Year>) -> Year {
+	let Some(pivot) = pivot else {
+		return year;
+	};
+	if (slot.len() != 2) || !slot.chars().all(|c| c.is_ascii_digit()) {
+		return year;
+	}
+	if year < pivot { 2000 + year } else { 1900 + year }
+}
+
 impl From<Recurring> for Fixed {
 	fn from(value: Recurring) -> Self {
-		Fixed{ year: Utc::now().year(), date: value }
+		Fixed{ year: Fixed::now().year(), date: value }
+	}
+}
+
+// fails if the year is beyond chrono's range (about 262,000 years either way)
+impl TryFrom<Fixed> for chrono::NaiveDate {
+	type Error = Error;
+	fn try_from(value: Fixed) -> Result<Self> {
+		match chrono::NaiveDate::from_ymd_opt(value.year, value.date.month, value.date.day) {
+			Some(date) => Ok(date),
+			None => Err(Error::OutOfRange{ what: "date out of chrono's range" }),
+		}
+	}
+}
+
+// chrono years all fit in a Year, hence infallible
+impl From<chrono::NaiveDate> for Fixed {
+	fn from(value: chrono::NaiveDate) -> Self {
+		Fixed::new(value.day(), value.month(), value.year())
 	}
 }
 
@@ -42,67 +144,252 @@ impl fmt::Display for Fixed {
 	}
 }
 
-impl TryFrom<&str> for Recurring {
+// parse an English weekday name, case-insensitive, at least its first 3 letters
+impl TryFrom<&str> for Weekday {
 	type Error = Error;
 	fn try_from(value: &str) -> Result<Self> {
-		let mut iter = value.split(',');
-		let Some(day) = iter.next() else {
-			return Err("missing 'day' slot");
-		};
-		let Some(month) = iter.next() else {
-			return Err("missing 'month' slot");
-		};
-		if iter.next().is_some() {
-			return Err("extra ',' found");
+		const WEEKDAYS: [Weekday; 7] = [
+			Weekday::Monday,
+			Weekday::Tuesday,
+			Weekday::Wednesday,
+			Weekday::Thursday,
+			Weekday::Friday,
+			Weekday::Saturday,
+			Weekday::Sunday,
+		];
+		let value = value.trim().to_lowercase();
+		if value.len() >= 3 {
+			if let Some(weekday) = WEEKDAYS.into_iter().find(|weekday| weekday.to_string().to_lowercase().starts_with(value.as_str())) {
+				return Ok(weekday);
+			}
 		}
-		let Ok(month) = month.trim().parse::<Month>() else {
-			return Err("failed to parse month");
-		};
-		let Ok(day) = day.trim().parse::<Day>() else {
-			return Err("failed to parse day");
+		Err(Error::Parse{ what: "failed to parse weekday" })
+	}
+}
+
+impl fmt::Display for Weekday {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let text = match self {
+			Weekday::Monday => "Monday",
+			Weekday::Tuesday => "Tuesday",
+			Weekday::Wednesday => "Wednesday",
+			Weekday::Thursday => "Thursday",
+			Weekday::Friday => "Friday",
+			Weekday::Saturday => "Saturday",
+			Weekday::Sunday => "Sunday",
 		};
-		Ok(Recurring{ month, day })
+		write!(f, "{}", text)
+	}
+}
+
+impl fmt::Display for Zodiac {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{:?}", self)
+	}
+}
+
+// return the western zodiac sign of a day & month
+pub fn zodiac(date: Recurring) -> Zodiac {
+	// first day of each sign, in calendar order
+	const FIRST_DAYS: [(Month, Day, Zodiac); 12] = [
+		(1, 20, Zodiac::Aquarius),
+		(2, 19, Zodiac::Pisces),
+		(3, 21, Zodiac::Aries),
+		(4, 20, Zodiac::Taurus),
+		(5, 21, Zodiac::Gemini),
+		(6, 21, Zodiac::Cancer),
+		(7, 23, Zodiac::Leo),
+		(8, 23, Zodiac::Virgo),
+		(9, 23, Zodiac::Libra),
+		(10, 23, Zodiac::Scorpio),
+		(11, 22, Zodiac::Sagittarius),
+		(12, 22, Zodiac::Capricorn),
+	];
+	FIRST_DAYS.iter()
+		.rev()
+		.find(|(month, day, _)| Recurring::new(*day, *month) <= date)
+		.map_or(Zodiac::Capricorn, |(_, _, sign)| *sign)
+}
+
+// describe <to> relatively to <from> when it is less than a week away
+// e.g. "today", "tomorrow" or "next Friday (in 3 days)"
+pub fn relative_phrase(from: Fixed, to: Fixed) -> String {
+	if to < from {
+		return format!("{}", to);
+	}
+	match from.to(to) {
+		0 => String::from("today"),
+		1 => String::from("tomorrow"),
+		days @ 2..=6 => format!("next {} (in {} days)", to.weekday(), days),
+		_ => format!("{}", to),
+	}
+}
+
+impl TryFrom<&str> for Recurring {
+	type Error = Error;
+	fn try_from(value: &str) -> Result<Self> {
+		DateParser::default().parse_recurring(value)
 	}
 }
 
 impl TryFrom<&str> for Fixed {
 	type Error = Error;
 	fn try_from(value: &str) -> Result<Self> {
-		let Some(first) = value.find(',') else {
-			return Err("missing first separator");
-		};
-		let Some(second) = value[first+1..].find(',') else {
-			return Err("missing second separator");
+		DateParser::default().parse_fixed(value)
+	}
+}
+
+impl TryFrom<&str> for AnyDate {
+	type Error = Error;
+	fn try_from(value: &str) -> Result<Self> {
+		DateParser::default().parse_any(value)
+	}
+}
+
+// parsing options shared by the date formats
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DateParser {
+	// between day, month and year
+	separator: char,
+	// accept English month names (e.g. "April" or "apr")
+	month_names: bool,
+	// two-digit years below the pivot are in the 2000s, the others in the 1900s
+	// the expansion is disabled when None
+	year_pivot: Option<Year>,
+}
+
+impl Default for DateParser {
+	fn default() -> Self {
+		DateParser {
+			separator: ',',
+			month_names: false,
+			year_pivot: None,
+		}
+	}
+}
+
+impl DateParser {
+
+	pub fn new() -> Self {
+		DateParser::default()
+	}
+
+	pub fn separator(mut self, separator: char) -> Self {
+		self.separator = separator;
+		self
+	}
+
+	pub fn month_names(mut self, month_names: bool) -> Self {
+		self.month_names = month_names;
+		self
+	}
+
+	pub fn year_pivot(mut self, year_pivot: Option<Year>) -> Self {
+		self.year_pivot = year_pivot;
+		self
+	}
+
+	fn parse_month(&self, value: &str) -> Result<Month> {
+		const NAMES: [&str; 12] = [
+			"january", "february", "march", "april", "may", "june",
+			"july", "august", "september", "october", "november", "december",
+		];
+		if let Ok(month) = value.parse::<Month>() {
+			return Ok(month);
+		}
+		if self.month_names && (value.len() >= 3) {
+			let value = value.to_lowercase();
+			if let Some(index) = NAMES.iter().position(|name| name.starts_with(value.as_str())) {
+				return Ok(index as Month + 1);
+			}
+		}
+		Err(Error::Parse{ what: "failed to parse month" })
+	}
+
+	fn parse_year(&self, value: &str) -> Result<Year> {
+		let Ok(year) = value.parse::<Year>() else {
+			return Err(Error::Parse{ what: "failed to parse year" });
 		};
-		let (date_slice, remaining) = value.split_at(first+1+second);
-		let date = Recurring::try_from(date_slice)?;
-		let mut iter = remaining.split(',');
-		let Some(_) = iter.next() else {
-			return Err("split_at failure");
+		Ok(expand_year(value, year, self.year_pivot))
+	}
+
+	// return the ISO 8601 "--month-day" or "year-month-day" slots of <value>, if in that format
+	// i.e. if the separator is not '-' and <value> holds a '-' past its first character
+	fn split_iso<'a>(&self, value: &'a str) -> Option<Vec<&'a str>> {
+		let value = value.trim();
+		if (self.separator == '-') || value.contains(self.separator) {
+			return None;
+		}
+		if let Some(value) = value.strip_prefix("--") {
+			return Some(value.split('-').collect());
+		}
+		let body = value.strip_prefix('-').unwrap_or(value);
+		if !body.contains('-') {
+			return None;
+		}
+		let sign = value.len() - body.len();
+		let mut slots: Vec<&str> = body.split('-').collect();
+		// keep the sign of a negative year
+		slots[0] = &value[..sign + slots[0].len()];
+		Some(slots)
+	}
+
+	// parse "day,month", or "--month-day" (ISO 8601)
+	pub fn parse_recurring(&self, value: &str) -> Result<Recurring> {
+		if let Some(slots) = self.split_iso(value).filter(|_| value.trim().starts_with("--")) {
+			let [month, day] = slots[..] else {
+				return Err(Error::Parse{ what: "expected --month-day" });
+			};
+			return self.separator(',').parse_recurring(&format!("{},{}", day, month));
+		}
+		let mut iter = value.split(self.separator);
+		let Some(day) = iter.next() else {
+			return Err(Error::Parse{ what: "missing 'day' slot" });
 		};
-		let Some(year) = iter.next() else {
-			return Err("missing 'year' slot");
+		let Some(month) = iter.next() else {
+			return Err(Error::Parse{ what: "missing 'month' slot" });
 		};
 		if iter.next().is_some() {
-			return Err("extra ',' found");
+			return Err(Error::Parse{ what: "extra separator found" });
+		}
+		let month = self.parse_month(month.trim())?;
+		if !(1..=12).contains(&month) {
+			return Err(Error::OutOfRange{ what: "month out of range" });
 		}
-		let Ok(year) = year.trim().parse::<Year>() else {
-			return Err("failed to parse year");
+		let Ok(day) = day.trim().parse::<Day>() else {
+			return Err(Error::Parse{ what: "failed to parse day" });
 		};
-		Ok(Fixed{ year, date })
+		Recurring::try_new(day, month)
 	}
-}
 
-impl TryFrom<&str> for AnyDate {
-	type Error = Error;
-	fn try_from(value: &str) -> Result<Self> {
-		if let Ok(recurring) = Recurring::try_from(value) {
+	// parse "day,month,year", or "year-month-day" (ISO 8601)
+	pub fn parse_fixed(&self, value: &str) -> Result<Fixed> {
+		if let Some(slots) = self.split_iso(value).filter(|_| !value.trim().starts_with("--")) {
+			let [year, month, day] = slots[..] else {
+				return Err(Error::Parse{ what: "expected year-month-day" });
+			};
+			return self.separator(',').parse_fixed(&format!("{},{},{}", day, month, year));
+		}
+		let Some((date, year)) = value.rsplit_once(self.separator) else {
+			return Err(Error::Parse{ what: "missing first separator" });
+		};
+		if !date.contains(self.separator) {
+			return Err(Error::Parse{ what: "missing second separator" });
+		}
+		let date = self.parse_recurring(date)?;
+		let year = self.parse_year(year.trim())?;
+		Fixed::try_new(date.day, date.month, year)
+	}
+
+	// parse either "day,month" or "day,month,year"
+	pub fn parse_any(&self, value: &str) -> Result<AnyDate> {
+		if let Ok(recurring) = self.parse_recurring(value) {
 			return Ok(AnyDate::Recurring(recurring));
 		};
-		if let Ok(fixed) = Fixed::try_from(value) {
+		if let Ok(fixed) = self.parse_fixed(value) {
 			return Ok(AnyDate::Fixed(fixed));
 		}
-		Err("no Date format matched")
+		Err(Error::Parse{ what: "no Date format matched" })
 	}
 }
 
@@ -112,16 +399,161 @@ impl Recurring {
 		Recurring{ month, day }
 	}
 
+	// same as new, but fails like the parser on a month or day out of range
+	// 29/02 is accepted, as it exists in the leap years
+	pub fn try_new(day: Day, month: Month) -> Result<Self> {
+		if !(1..=12).contains(&month) {
+			return Err(Error::OutOfRange{ what: "month out of range" });
+		}
+		// 2000 is leap
+		if (day == 0) || (day > last_day(month, 2000)) {
+			return Err(Error::OutOfRange{ what: "day out of range for month" });
+		}
+		Ok(Recurring{ month, day })
+	}
+
 	pub fn now() -> Self {
-		let now = Utc::now();
-		Recurring::new(now.day(), now.month())
+		Fixed::now().recurring()
+	}
+
+	pub fn day(self) -> Day {
+		self.day
+	}
+
+	// return the .rce representation, "day,month"
+	pub fn to_rce(self) -> String {
+		format!("{:02},{:02}", self.day, self.month)
+	}
+
+	// return the same day <months> months later, wrapping around the year
+	// the day is clamped to the last day of the month (29/02 being allowed)
+	pub fn add_months(self, months: u32) -> Self {
+		let month = (self.month - 1 + months) % 12 + 1;
+		let day = self.day.min(last_day(month, 2000));
+		Recurring::new(day, month)
+	}
+
+	// return this day & month in <year>, 29/02 clamped to 28/02 if needed
+	pub fn in_year(self, year: Year) -> Fixed {
+		Fixed{ year, date: self }.clamp_leap_day()
+	}
+
+	// return this day & month in <year>, 29/02 handled according to <policy>
+	// None if the policy skips it
+	pub fn in_year_with(self, year: Year, policy: LeapDayPolicy) -> Option<Fixed> {
+		let date = Fixed{ year, date: self };
+		match policy {
+			LeapDayPolicy::Feb28 => Some(date.clamp_leap_day()),
+			LeapDayPolicy::Skip if date.clamp_leap_day() != date => None,
+			LeapDayPolicy::Skip => Some(date),
+			LeapDayPolicy::Mar1 if date.clamp_leap_day() != date => Some(Fixed::new(1, 3, year)),
+			LeapDayPolicy::Mar1 => Some(date),
+		}
+	}
+
+	pub fn month(self) -> Month {
+		self.month
+	}
+}
+
+impl Weekly {
+
+	// return the first occurrence on or after <from>
+	pub fn next_on_or_after(self, from: Fixed) -> Fixed {
+		(0..7)
+			.map(|days| from.add_days(days))
+			.find(|date| date.weekday() == self.weekday)
+			.unwrap_or(from)
+	}
+
+	// return the first occurrence from now on
+	pub fn next_match(self) -> Fixed {
+		self.next_on_or_after(Fixed::now())
+	}
+}
+
+impl Rule {
+
+	// return the occurrence in <year>, None if there is none
+	pub fn in_year(self, year: Year) -> Option<Fixed> {
+		match self {
+			Rule::Easter{ offset_days } => Some(easter(year).add_days(offset_days)),
+			Rule::NthWeekday{ month, weekday, nth } => nth_weekday(year, month, weekday, nth),
+			Rule::EveryYears{ since, years } => {
+				let elapsed = year as i64 - since.year as i64;
+				if (elapsed < 0) || (elapsed % years.max(1) as i64 != 0) {
+					return None;
+				}
+				Some(since.date.in_year(year))
+			},
+		}
+	}
+
+	// return the first occurrence on or after <from>, looking up to 400 occurrences ahead
+	// a 5th weekday may be missing for years, it comes back within a 400 years cycle
+	pub fn next_on_or_after(self, from: Fixed) -> Option<Fixed> {
+		let (first, step) = match self {
+			// the years without an occurrence are skipped at once
+			Rule::EveryYears{ since, years } => {
+				let years = years.max(1) as i64;
+				let behind = (from.year as i64 - since.year as i64).max(0);
+				let first = Year::try_from(since.year as i64 + behind - behind % years).unwrap_or(from.year);
+				(first, years as usize)
+			},
+			_ => (from.year, 1),
+		};
+		(first..=Year::MAX)
+			.step_by(step)
+			.take(400)
+			.filter_map(|year| self.in_year(year))
+			.find(|date| *date >= from)
+	}
+}
+
+impl TryFrom<&str> for Weekly {
+	type Error = Error;
+	fn try_from(value: &str) -> Result<Self> {
+		Ok(Weekly{ weekday: Weekday::try_from(value)? })
+	}
+}
+
+impl AnyDate {
+
+	// return the .rce representation, with or without the year
+	pub fn to_rce(self) -> String {
+		match self {
+			AnyDate::Recurring(recurring) => recurring.to_rce(),
+			AnyDate::Fixed(fixed) => fixed.to_rce(),
+		}
+	}
+
+	// return the day & month, without the year if any
+	pub fn recurring(self) -> Recurring {
+		match self {
+			AnyDate::Recurring(recurring) => recurring,
+			AnyDate::Fixed(fixed) => fixed.date,
+		}
+	}
+
+	// return the upcoming date with the same day & month, see Fixed::next_match()
+	// e.g. to sort dates with and without a year by when they next happen
+	pub fn next_occurrence(self) -> Fixed {
+		match self {
+			AnyDate::Recurring(recurring) => Fixed::from(recurring).next_match(),
+			AnyDate::Fixed(fixed) => fixed.next_match(),
+		}
+	}
+
+	// same as next_occurrence, on or after <today>, 29/02 moved to 28/02 if needed
+	pub fn next_occurrence_on(self, today: Fixed) -> Fixed {
+		Fixed{ year: today.year, date: self.recurring() }.next_match_on(today, LeapDayPolicy::Feb28)
 	}
 }
 
 fn is_leap(year: Year) -> bool {
-	if year % 400 == 0 { return true; }
-	if year % 100 == 0 { return false; }
-	if year %   4 == 0 { return true; }
+	if year.rem_euclid(400) == 0 { return true; }
+	if year.rem_euclid(100) == 0 { return false; }
+	if year.rem_euclid(  4) == 0 { return true; }
 	false
 }
 
@@ -134,63 +566,332 @@ fn last_day(month: Month, year: Year) -> Day {
 	}
 }
 
+// return the <nth> <weekday> of <month> in <year>, counted from the end if <nth> is negative
+// e.g. 4th Thursday or -1st (last) Monday, None if there is no such day
+pub fn nth_weekday(year: Year, month: Month, weekday: Weekday, nth: i32) -> Option<Fixed> {
+	if !(1..=12).contains(&month) {
+		return None;
+	}
+	let last = last_day(month, year) as i32;
+	let day = match nth {
+		0 => return None,
+		1.. => {
+			let mut first = Fixed::new(1, month, year);
+			while first.weekday() != weekday {
+				first = first.next();
+			}
+			first.date.day as i32 + 7 * (nth - 1)
+		},
+		_ => {
+			let mut last_one = Fixed::new(last as Day, month, year);
+			while last_one.weekday() != weekday {
+				last_one = last_one.add_days(-1);
+			}
+			last_one.date.day as i32 + 7 * (nth + 1)
+		},
+	};
+	if (day < 1) || (day > last) {
+		return None;
+	}
+	Some(Fixed::new(day as Day, month, year))
+}
+
+// return the date of Easter Sunday in <year>, Anonymous Gregorian algorithm
+pub fn easter(year: Year) -> Fixed {
+	let a = year.rem_euclid(19);
+	let b = year.div_euclid(100);
+	let c = year.rem_euclid(100);
+	let d = b / 4;
+	let e = b % 4;
+	let f = (b + 8) / 25;
+	let g = (b - f + 1) / 3;
+	let h = (19 * a + b - d - g + 15).rem_euclid(30);
+	let i = c / 4;
+	let k = c % 4;
+	let l = (32 + 2 * e + 2 * i - h - k).rem_euclid(7);
+	let m = (a + 11 * h + 22 * l) / 451;
+	let month = (h + l - 7 * m + 114) / 31;
+	let day = (h + l - 7 * m + 114) % 31 + 1;
+	Fixed::new(day as Day, month as Month, year)
+}
+
+// iterate over every date from <begin> to <end>, both included
+pub fn dates_in_range(begin: Fixed, end: Fixed) -> impl Iterator<Item = Fixed> {
+	std::iter::successors(Some(begin), |date| date.checked_next())
+		.take_while(move |date| *date <= end)
+}
+
 impl Fixed {
 
 	pub fn new(day: Day, month: Month, year: Year) -> Self {
 		Fixed { year, date: Recurring::new(day, month) }
 	}
 
+	// same as new, but fails like the parser on a date that does not exist, e.g. 29/02/2023
+	pub fn try_new(day: Day, month: Month, year: Year) -> Result<Self> {
+		let date = Recurring::try_new(day, month)?;
+		if day > last_day(month, year) {
+			return Err(Error::OutOfRange{ what: "day out of range for month" });
+		}
+		Ok(Fixed{ year, date })
+	}
+
 	pub fn now() -> Self {
 		let now = Utc::now();
 		Fixed::new(now.day(), now.month(), now.year())
 	}
 
-	// return then next day
+	pub fn year(self) -> Year {
+		self.year
+	}
+
+	// return the .rce representation, "day,month,year"
+	pub fn to_rce(self) -> String {
+		format!("{},{:04}", self.date.to_rce(), self.year)
+	}
+
+	// return the day & month, without the year
+	pub fn recurring(self) -> Recurring {
+		self.date
+	}
+
+	// return the date <days> days later, or earlier if negative
+	// the first or last representable day if that date is out of range
+	pub fn add_days(self, days: i32) -> Self {
+		match Fixed::from_ordinal(self.ordinal() + days as i64) {
+			Some(date) => date,
+			None if days > 0 => Fixed::new(31, 12, Year::MAX),
+			None => Fixed::new(1, 1, Year::MIN),
+		}
+	}
+
+	// return then next day, the same day if it is the last representable one
 	pub fn next(self) -> Self {
+		self.checked_next().unwrap_or(self)
+	}
+
+	// return the next day, None after 31/12 of the last representable year
+	pub fn checked_next(self) -> Option<Self> {
 		let mut next = self;
-		next.date.day = next.date.day.checked_add(1).unwrap();
+		next.date.day += 1;
 		if next.date.day > last_day(next.date.month, next.year) {
 			next.date.day = 1;
-			next.date.month = next.date.month.checked_add(1).unwrap();
+			next.date.month += 1;
 		}
 		if next.date.month > 12 {
 			next.date.day = 1;
 			next.date.month = 1;
-			next.year = next.year.checked_add(1).unwrap(); // might panic
+			next.year = next.year.checked_add(1)?;
+		}
+		Some(next)
+	}
+
+	// return the previous day, the same day if it is the first representable one
+	pub fn prev(self) -> Self {
+		self.checked_prev().unwrap_or(self)
+	}
+
+	// return the previous day, None before 01/01 of the first representable year
+	pub fn checked_prev(self) -> Option<Self> {
+		let mut prev = self;
+		if prev.date.day > 1 {
+			prev.date.day -= 1;
+			return Some(prev);
+		}
+		if prev.date.month > 1 {
+			prev.date.month -= 1;
+		} else {
+			prev.date.month = 12;
+			prev.year = prev.year.checked_sub(1)?;
+		}
+		prev.date.day = last_day(prev.date.month, prev.year);
+		Some(prev)
+	}
+
+	// return 366 for leap years, 365 otherwise
+	pub fn days_in_year(self) -> u32 {
+		if is_leap(self.year) { 366 } else { 365 }
+	}
+
+	// return the position of the day in its year, starting at 1
+	pub fn day_of_year(self) -> u32 {
+		let before: u32 = (1..self.date.month).map(|month| last_day(month, self.year)).sum();
+		before + self.date.day
+	}
+
+	// return the date spelled out in <locale>, e.g. "lundi 25 décembre 2023"
+	pub fn format_localized(&self, locale: super::locale::Locale) -> String {
+		let messages = locale.messages();
+		let month = messages.months[(self.date.month - 1) as usize];
+		format!("{} {} {} {}", messages.weekday(self.weekday()), self.date.day, month, self.year)
+	}
+
+	// return the day of the week (Sakamoto's method)
+	pub fn weekday(self) -> Weekday {
+		const OFFSET: [i64; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+		const WEEKDAYS: [Weekday; 7] = [
+			Weekday::Sunday,
+			Weekday::Monday,
+			Weekday::Tuesday,
+			Weekday::Wednesday,
+			Weekday::Thursday,
+			Weekday::Friday,
+			Weekday::Saturday,
+		];
+		let month = self.date.month.clamp(1, 12) as usize;
+		let year = self.year as i64 - if month < 3 { 1 } else { 0 };
+		let index = year + year.div_euclid(4) - year.div_euclid(100) + year.div_euclid(400)
+			+ OFFSET[month-1] + self.date.day as i64;
+		WEEKDAYS[index.rem_euclid(7) as usize]
+	}
+
+	// return the day taken off to bridge a holiday to the week-end
+	// Monday for a Tuesday, Friday for a Thursday, None otherwise
+	pub fn bridge_day(self) -> Option<Self> {
+		match self.weekday() {
+			Weekday::Tuesday => Some(self.prev()),
+			Weekday::Thursday => Some(self.next()),
+			_ => None,
 		}
-		next
 	}
 
 	// return the sole date that have the same day & month
 	// in the range [now, now+1*year)
-	// 29/02 map to 28/02 if the range does not contain it
+	// 29/02 is moved to 28/02 in the years that are not leap, see next_match_with()
 	pub fn next_match(self) -> Self {
-		let mut next = Fixed::from(self.date);
-		if next < Fixed::now() {
-			next.year += 1;
+		self.next_match_with(LeapDayPolicy::default())
+	}
+
+	// same as next_match, 29/02 handled according to <policy>
+	// the skipped 29/02 match the next leap year, possibly more than a year away
+	pub fn next_match_with(self, policy: LeapDayPolicy) -> Self {
+		self.next_match_on(Fixed::now(), policy)
+	}
+
+	// same as next_match_with, in the range [today, today+1*year) instead
+	// <today> itself if no match comes on or after it in the representable years
+	pub fn next_match_on(self, today: Fixed, policy: LeapDayPolicy) -> Self {
+		self.checked_next_match_on(today, policy).unwrap_or(today)
+	}
+
+	// same as next_match_on, None if no match comes on or after <today> in the representable years
+	pub fn checked_next_match_on(self, today: Fixed, policy: LeapDayPolicy) -> Option<Self> {
+		let mut year = today.year;
+		loop {
+			match self.date.in_year_with(year, policy) {
+				Some(next) if next >= today => return Some(next),
+				_ => year = year.checked_add(1)?,
+			}
+		}
+	}
+
+	// return the last date before now with the same day & month, the mirror of next_match
+	// 29/02 is moved to 28/02 in the years that are not leap, see prev_match_with()
+	pub fn prev_match(self) -> Self {
+		self.prev_match_with(LeapDayPolicy::default())
+	}
+
+	// same as prev_match, 29/02 handled according to <policy>
+	pub fn prev_match_with(self, policy: LeapDayPolicy) -> Self {
+		self.prev_match_on(Fixed::now(), policy)
+	}
+
+	// same as prev_match_with, the last date before <today> instead
+	// <today> itself if no match comes before it in the representable years
+	pub fn prev_match_on(self, today: Fixed, policy: LeapDayPolicy) -> Self {
+		self.checked_prev_match_on(today, policy).unwrap_or(today)
+	}
+
+	// same as prev_match_on, None if no match comes before <today> in the representable years
+	pub fn checked_prev_match_on(self, today: Fixed, policy: LeapDayPolicy) -> Option<Self> {
+		let mut year = today.year;
+		loop {
+			match self.date.in_year_with(year, policy) {
+				Some(prev) if prev < today => return Some(prev),
+				_ => year = year.checked_sub(1)?,
+			}
 		}
-		if (next.date == Recurring::new(29,2)) && (!is_leap(next.year)) {
-			next.date = Recurring::new(28,2)
+	}
+
+	// map 29/02 to 28/02 if the year is not leap
+	pub fn clamp_leap_day(self) -> Self {
+		if (self.date == Recurring::new(29,2)) && (!is_leap(self.year)) {
+			return Fixed::new(28, 2, self.year);
 		}
-		next
+		self
+	}
+
+	// return the next match and the number of years since self, i.e. the age reached on it
+	// 29/02 is observed on 28/02 in the years that are not leap, the age counting from then
+	// the years are counted from the matched year so the clamped day cannot shift the age
+	// an error if the next match or the number of years is out of range
+	pub fn next_anniversary(self) -> Result<(Self, i32)> {
+		self.next_anniversary_with(LeapDayPolicy::default())
+	}
+
+	// same as next_anniversary, 29/02 handled according to <policy>
+	pub fn next_anniversary_with(self, policy: LeapDayPolicy) -> Result<(Self, i32)> {
+		self.next_anniversary_on(Fixed::now(), policy)
+	}
+
+	// same as next_anniversary_with, the next match being on or after <today>
+	pub fn next_anniversary_on(self, today: Fixed, policy: LeapDayPolicy) -> Result<(Self, i32)> {
+		let Some(next) = self.checked_next_match_on(today, policy) else {
+			return Err(Error::OutOfRange{ what: "no next match in the representable years" });
+		};
+		Ok((next, self.year_diff(next)?))
+	}
+
+	// return the number of years between self and its next match
+	// i.e. the age reached on the next birthday, see next_anniversary()
+	pub fn years_until_next_match(self) -> Result<i32> {
+		Ok(self.next_anniversary()?.1)
 	}
 
 	// return the number of years between self and target
-	// useful when target = self.next_match()
-	pub fn year_diff(self, target: Self) -> i32 {
-		target.year - self.year
+	// useful when target = self.next_match(), an error if it does not fit a Year
+	pub fn year_diff(self, target: Self) -> Result<i32> {
+		match target.year.checked_sub(self.year) {
+			Some(diff) => Ok(diff),
+			None => Err(Error::OutOfRange{ what: "years apart out of range" }),
+		}
+	}
+
+	// return the number of days since 01/01/1970 (proleptic Gregorian calendar)
+	fn ordinal(self) -> i64 {
+		let month = self.date.month as i64;
+		let day = self.date.day as i64;
+		let year = self.year as i64 - if month <= 2 { 1 } else { 0 };
+		let era = year.div_euclid(400);
+		let year_of_era = year.rem_euclid(400);
+		let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+		let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+		era * 146097 + day_of_era - 719468
+	}
+
+	// inverse of ordinal, None if the year is out of range
+	fn from_ordinal(ordinal: i64) -> Option<Self> {
+		let shifted = ordinal + 719468;
+		let era = shifted.div_euclid(146097);
+		let day_of_era = shifted.rem_euclid(146097);
+		let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+		let day_of_year = day_of_era - (year_of_era * 365 + year_of_era / 4 - year_of_era / 100);
+		// months counted from march, as in ordinal
+		let shifted_month = (5 * day_of_year + 2) / 153;
+		let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+		let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+		let year = era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+		Some(Fixed{ year: Year::try_from(year).ok()?, date: Recurring{ month: month as Month, day: day as Day } })
+	}
+
+	// return the number of days from self to target, negative if target is before self
+	pub fn signed_to(self, target: Self) -> i64 {
+		target.ordinal() - self.ordinal()
 	}
 
-	// TODO:
-	// return the number of days from self to target
+	// return the number of days from self to target, 0 if target is before self
 	pub fn to(self, target: Self) -> u32 {
-		let mut current = self;
-		let mut count: u32 = 0;
-		while current < target {
-			current = current.next();
-			count = count.checked_add(1).unwrap();
-		}
-		count
+		u32::try_from(self.signed_to(target).max(0)).unwrap_or(u32::MAX)
 	}
 }
 
@@ -247,22 +948,56 @@ mod test {
 		);
 	}
 	#[test]
-	fn recurring_compare_less() {
-		let base = Recurring::new(1, 1);
-		let different_month = Recurring::new(1, 2);
-		let different_day   = Recurring::new(2, 1);
-		assert_eq!(
-			base.cmp(&different_month),
-			Ordering::Less
+	fn recurring_parse_day_out_of_range() {
+		assert!(
+			Recurring::try_from("31,4")
+			.is_err()
+		);
+		assert!(
+			Recurring::try_from("30,2")
+			.is_err()
+		);
+		assert!(
+			Recurring::try_from("0,1")
+			.is_err()
 		);
 		assert_eq!(
-			base.cmp(&different_day),
-			Ordering::Less
+			Recurring::try_from("29,2").unwrap(),
+			Recurring::new(29,2)
 		);
 	}
 	#[test]
-	fn recurring_compare_equal() {
-		let base = Recurring::new(1, 1);
+	fn recurring_parse_month_out_of_range() {
+		assert_eq!(
+			Recurring::try_from("1,0"),
+			Err(Error::OutOfRange{ what: "month out of range" })
+		);
+		assert_eq!(
+			Recurring::try_from("1,13"),
+			Err(Error::OutOfRange{ what: "month out of range" })
+		);
+		assert_eq!(
+			Recurring::try_from("32,13"),
+			Err(Error::OutOfRange{ what: "month out of range" })
+		);
+	}
+	#[test]
+	fn recurring_compare_less() {
+		let base = Recurring::new(1, 1);
+		let different_month = Recurring::new(1, 2);
+		let different_day   = Recurring::new(2, 1);
+		assert_eq!(
+			base.cmp(&different_month),
+			Ordering::Less
+		);
+		assert_eq!(
+			base.cmp(&different_day),
+			Ordering::Less
+		);
+	}
+	#[test]
+	fn recurring_compare_equal() {
+		let base = Recurring::new(1, 1);
 		assert_eq!(
 			base.cmp(&base),
 			Ordering::Equal
@@ -283,6 +1018,121 @@ mod test {
 		);
 	}
 	#[test]
+	fn zodiac_cusps() {
+		assert_eq!(
+			zodiac(Recurring::new(20,3)),
+			Zodiac::Pisces
+		);
+		assert_eq!(
+			zodiac(Recurring::new(21,3)),
+			Zodiac::Aries
+		);
+		assert_eq!(
+			zodiac(Recurring::new(19,1)),
+			Zodiac::Capricorn
+		);
+		assert_eq!(
+			zodiac(Recurring::new(20,1)),
+			Zodiac::Aquarius
+		);
+		assert_eq!(
+			zodiac(Recurring::new(21,12)),
+			Zodiac::Sagittarius
+		);
+		assert_eq!(
+			zodiac(Recurring::new(22,12)),
+			Zodiac::Capricorn
+		);
+	}
+	#[test]
+	fn recurring_add_months() {
+		assert_eq!(
+			Recurring::new(15,11).add_months(6),
+			Recurring::new(15,5)
+		);
+		assert_eq!(
+			Recurring::new(31,8).add_months(6),
+			Recurring::new(29,2)
+		);
+		assert_eq!(
+			Recurring::new(31,3).add_months(6),
+			Recurring::new(30,9)
+		);
+		assert_eq!(
+			Recurring::new(1,1).add_months(12),
+			Recurring::new(1,1)
+		);
+	}
+	#[test]
+	fn recurring_in_year_with() {
+		assert_eq!(
+			Recurring::new(29,2).in_year_with(2023, LeapDayPolicy::Feb28),
+			Some(Fixed::new(28,2,2023))
+		);
+		assert_eq!(
+			Recurring::new(29,2).in_year_with(2023, LeapDayPolicy::Skip),
+			None
+		);
+		assert_eq!(
+			Recurring::new(29,2).in_year_with(2024, LeapDayPolicy::Skip),
+			Some(Fixed::new(29,2,2024))
+		);
+		assert_eq!(
+			Recurring::new(1,3).in_year_with(2023, LeapDayPolicy::Skip),
+			Some(Fixed::new(1,3,2023))
+		);
+		assert_eq!(
+			Recurring::new(29,2).in_year_with(2023, LeapDayPolicy::Mar1),
+			Some(Fixed::new(1,3,2023))
+		);
+		assert_eq!(
+			Recurring::new(29,2).in_year_with(2024, LeapDayPolicy::Mar1),
+			Some(Fixed::new(29,2,2024))
+		);
+	}
+	#[test]
+	fn fixed_next_match_with() {
+		let born = Fixed::new(29,2,2000);
+		let today = Fixed::new(1,1,2023);
+		assert_eq!(
+			born.next_match_on(today, LeapDayPolicy::Feb28),
+			Fixed::new(28,2,2023)
+		);
+		assert_eq!(
+			born.next_match_on(today, LeapDayPolicy::Mar1),
+			Fixed::new(1,3,2023)
+		);
+		assert_eq!(
+			born.next_match_on(today, LeapDayPolicy::Skip),
+			Fixed::new(29,2,2024)
+		);
+		let today = Fixed::new(1,3,2023);
+		assert_eq!(
+			born.next_match_on(today, LeapDayPolicy::Feb28),
+			Fixed::new(29,2,2024)
+		);
+		assert_eq!(
+			born.next_match_on(today, LeapDayPolicy::Mar1),
+			Fixed::new(1,3,2023)
+		);
+		assert_eq!(
+			born.next_anniversary_on(today, LeapDayPolicy::Mar1),
+			Ok((Fixed::new(1,3,2023), 23))
+		);
+		assert!(born.next_match_with(LeapDayPolicy::Skip) >= Fixed::now());
+	}
+	#[test]
+	fn recurring_in_year() {
+		assert_eq!(
+			Recurring::new(29,2).in_year(2023),
+			Fixed::new(28,2,2023)
+		);
+		assert_eq!(
+			Recurring::new(29,2).in_year(2024),
+			Fixed::new(29,2,2024)
+		);
+	}
+	#[test]
 	fn recurring_compare_today() {
 		let today = Recurring::now();
 		let today = Fixed::from(today);
@@ -341,6 +1191,232 @@ mod test {
 		);
 	}
 	#[test]
+	fn fixed_parse_two_digit_year_disabled() {
+		assert_eq!(
+			Fixed::try_from("25,12,99").unwrap(),
+			Fixed::new(25,12,99)
+		);
+	}
+	#[test]
+	fn fixed_parse_two_digit_year_pivot() {
+		let parser = DateParser::new().year_pivot(Some(70));
+		assert_eq!(
+			parser.parse_fixed("25,12,99").unwrap(),
+			Fixed::new(25,12,1999)
+		);
+		assert_eq!(
+			parser.parse_fixed("25,12,70").unwrap(),
+			Fixed::new(25,12,1970)
+		);
+		assert_eq!(
+			parser.parse_fixed("25,12,69").unwrap(),
+			Fixed::new(25,12,2069)
+		);
+		assert_eq!(
+			parser.parse_fixed("25,12,25").unwrap(),
+			Fixed::new(25,12,2025)
+		);
+	}
+	#[test]
+	fn fixed_parse_four_digit_year_pivot() {
+		let parser = DateParser::new().year_pivot(Some(70));
+		assert_eq!(
+			parser.parse_fixed("25,12,2099").unwrap(),
+			Fixed::new(25,12,2099)
+		);
+		assert_eq!(
+			parser.parse_fixed("25,12,1925").unwrap(),
+			Fixed::new(25,12,1925)
+		);
+	}
+	#[test]
+	fn fixed_parse_day_out_of_range() {
+		assert!(
+			Fixed::try_from("31,2,2023")
+			.is_err()
+		);
+		assert!(
+			Fixed::try_from("29,2,2023")
+			.is_err()
+		);
+		assert_eq!(
+			Fixed::try_from("29,2,2024").unwrap(),
+			Fixed::new(29,2,2024)
+		);
+	}
+	#[test]
+	fn fixed_next_match_on() {
+		let today = Fixed::new(24,12,2030);
+		assert_eq!(
+			Fixed::from(Recurring::new(1,1)).next_match_on(today, LeapDayPolicy::Feb28),
+			Fixed::new(1,1,2031)
+		);
+		assert_eq!(
+			Fixed::new(24,12,1990).next_match_on(today, LeapDayPolicy::Feb28),
+			today
+		);
+	}
+	#[test]
+	fn fixed_naive_date_round_trip() {
+		for fixed in [
+			Fixed::new(1,1,2030),
+			Fixed::new(29,2,2024),
+			Fixed::new(31,12,1999),
+			Fixed::new(15,3,-44),
+		] {
+			let naive = chrono::NaiveDate::try_from(fixed).unwrap();
+			assert_eq!(
+				(naive.day(), naive.month(), naive.year()),
+				(fixed.recurring().day(), fixed.recurring().month(), fixed.year())
+			);
+			assert_eq!(
+				Fixed::from(naive),
+				fixed
+			);
+		}
+		assert_eq!(
+			Fixed::from(chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()),
+			Fixed::new(29,2,2024)
+		);
+		assert_eq!(
+			Fixed::from(chrono::NaiveDate::MIN),
+			Fixed::new(chrono::NaiveDate::MIN.day(), chrono::NaiveDate::MIN.month(), chrono::NaiveDate::MIN.year())
+		);
+	}
+	#[test]
+	fn fixed_naive_date_out_of_range() {
+		for fixed in [Fixed::new(1,1,i32::MAX), Fixed::new(31,12,i32::MIN), Fixed::new(1,1,300000)] {
+			assert_eq!(
+				chrono::NaiveDate::try_from(fixed),
+				Err(Error::OutOfRange{ what: "date out of chrono's range" })
+			);
+		}
+	}
+	#[test]
+	fn parse_iso() {
+		assert_eq!(
+			Fixed::try_from("2023-04-09"),
+			Fixed::try_from("9,4,2023")
+		);
+		assert_eq!(
+			Fixed::try_from(" 2024-02-29 "),
+			Ok(Fixed::new(29,2,2024))
+		);
+		assert_eq!(
+			Fixed::try_from("-0044-03-15"),
+			Ok(Fixed::new(15,3,-44))
+		);
+		assert_eq!(
+			Recurring::try_from("--12-25"),
+			Recurring::try_from("25,12")
+		);
+		assert_eq!(
+			AnyDate::try_from("--12-25"),
+			Ok(AnyDate::Recurring(Recurring::new(25,12)))
+		);
+		assert_eq!(
+			AnyDate::try_from("2023-04-09"),
+			Ok(AnyDate::Fixed(Fixed::new(9,4,2023)))
+		);
+		assert_eq!(
+			Fixed::try_from("1,1,-44"),
+			Ok(Fixed::new(1,1,-44))
+		);
+		assert_eq!(
+			Fixed::try_from("2023-02-29"),
+			Err(Error::OutOfRange{ what: "day out of range for month" })
+		);
+		assert!(
+			Fixed::try_from("2023-04")
+			.is_err()
+		);
+		assert!(
+			Recurring::try_from("2023-04-09")
+			.is_err()
+		);
+	}
+	#[test]
+	fn any_date_next_occurrence() {
+		let now = Fixed::new(1,6,2030);
+		let early = AnyDate::Recurring(Recurring::new(1,3));
+		let late = AnyDate::Fixed(Fixed::new(1,10,1990));
+		let today = AnyDate::Recurring(Recurring::new(1,6));
+		assert_eq!(
+			early.next_occurrence_on(now),
+			Fixed::new(1,3,2031)
+		);
+		assert_eq!(
+			late.next_occurrence_on(now),
+			Fixed::new(1,10,2030)
+		);
+		let mut dates = [early, late, today];
+		dates.sort_by_key(|date| date.next_occurrence_on(now));
+		assert_eq!(
+			dates,
+			[today, late, early]
+		);
+	}
+	#[test]
+	fn hash_map_keys() {
+		use std::collections::{HashMap, hash_map::DefaultHasher};
+		use std::hash::{Hash, Hasher};
+		let mut map: HashMap<Fixed, Vec<&str>> = HashMap::new();
+		map.entry(Fixed::new(25,12,2030)).or_default().push("Christmas");
+		map.entry(Fixed::new(25,12,2030)).or_default().push("Noël");
+		map.entry(Fixed::new(25,12,2031)).or_default().push("later");
+		assert_eq!(
+			map.get(&Fixed::new(25,12,2030)),
+			Some(&vec!["Christmas", "Noël"])
+		);
+		fn hash<T: Hash>(value: T) -> u64 {
+			let mut hasher = DefaultHasher::new();
+			value.hash(&mut hasher);
+			hasher.finish()
+		}
+		assert_eq!(
+			hash(Recurring::new(1,5)),
+			hash(Recurring::try_from("01,05").unwrap())
+		);
+		assert_eq!(
+			hash(AnyDate::Recurring(Recurring::new(1,5))),
+			hash(AnyDate::try_from("1,5").unwrap())
+		);
+	}
+	#[test]
+	fn fixed_prev_match() {
+		let now = Fixed::new(2,1,2030);
+		let new_year = Fixed::new(1,1,1990).prev_match_on(now, LeapDayPolicy::Feb28);
+		let christmas = Fixed::new(25,12,1990).prev_match_on(now, LeapDayPolicy::Feb28);
+		let today = Fixed::new(2,1,1990).prev_match_on(now, LeapDayPolicy::Feb28);
+		let now = Fixed::new(1,1,2030);
+		let new_year_today = Fixed::new(1,1,1990).prev_match_on(now, LeapDayPolicy::Feb28);
+		let leap_day = Fixed::new(29,2,2000).prev_match_on(now, LeapDayPolicy::Feb28);
+		let leap_day_skipped = Fixed::new(29,2,2000).prev_match_on(now, LeapDayPolicy::Skip);
+		assert_eq!(
+			(new_year, christmas, today),
+			(Fixed::new(1,1,2030), Fixed::new(25,12,2029), Fixed::new(2,1,2029))
+		);
+		assert_eq!(
+			new_year_today,
+			Fixed::new(1,1,2029)
+		);
+		assert_eq!(
+			(leap_day, leap_day_skipped),
+			(Fixed::new(28,2,2029), Fixed::new(29,2,2028))
+		);
+	}
+	#[test]
+	fn fixed_parse_month_out_of_range() {
+		assert_eq!(
+			Fixed::try_from("1,0,2023"),
+			Err(Error::OutOfRange{ what: "month out of range" })
+		);
+		assert_eq!(
+			Fixed::try_from("1,13,2023"),
+			Err(Error::OutOfRange{ what: "month out of range" })
+		);
+	}
+	#[test]
 	fn fixed_compare_less() {
 		let base = Fixed::new(1, 1, 1);
 		let different_year  = Fixed::new(1, 1, 2);
@@ -416,6 +1492,353 @@ mod test {
 		);
 	}
 
+	// test relative_phrase
+	#[test]
+	fn relative_today() {
+		let from = Fixed::new(25,12,2024);
+		assert_eq!(
+			relative_phrase(from, from),
+			"today"
+		);
+	}
+	#[test]
+	fn relative_tomorrow() {
+		assert_eq!(
+			relative_phrase(Fixed::new(31,12,2024), Fixed::new(1,1,2025)),
+			"tomorrow"
+		);
+	}
+	#[test]
+	fn relative_weekday() {
+		assert_eq!(
+			relative_phrase(Fixed::new(25,12,2024), Fixed::new(27,12,2024)),
+			"next Friday (in 2 days)"
+		);
+		assert_eq!(
+			relative_phrase(Fixed::new(25,12,2024), Fixed::new(31,12,2024)),
+			"next Tuesday (in 6 days)"
+		);
+	}
+	#[test]
+	fn relative_absolute() {
+		assert_eq!(
+			relative_phrase(Fixed::new(25,12,2024), Fixed::new(1,1,2025)),
+			"01/01/2025"
+		);
+		assert_eq!(
+			relative_phrase(Fixed::new(25,12,2024), Fixed::new(24,12,2024)),
+			"24/12/2024"
+		);
+	}
+
+	#[test]
+	fn weekly_next() {
+		// 1/1/2024 is a Monday
+		let monday = Fixed::new(1,1,2024);
+		assert_eq!(
+			Weekly::try_from("Tue").unwrap().next_on_or_after(monday),
+			Fixed::new(2,1,2024)
+		);
+		assert_eq!(
+			Weekly::try_from("Mon").unwrap().next_on_or_after(monday),
+			monday
+		);
+		assert_eq!(
+			Weekly::try_from("Sun").unwrap().next_on_or_after(monday),
+			Fixed::new(7,1,2024)
+		);
+		assert!(
+			Weekly::try_from("Tu")
+			.is_err()
+		);
+	}
+
+	// test nth_weekday
+	#[test]
+	fn nth_weekday_known_dates() {
+		assert_eq!(
+			nth_weekday(2024, 11, Weekday::Thursday, 4),
+			Some(Fixed::new(28,11,2024))
+		);
+		assert_eq!(
+			nth_weekday(2024, 5, Weekday::Monday, -1),
+			Some(Fixed::new(27,5,2024))
+		);
+		assert_eq!(
+			nth_weekday(2024, 5, Weekday::Wednesday, 1),
+			Some(Fixed::new(1,5,2024))
+		);
+		assert_eq!(
+			nth_weekday(2024, 5, Weekday::Monday, 5),
+			None
+		);
+		assert_eq!(
+			nth_weekday(2024, 5, Weekday::Monday, 0),
+			None
+		);
+		assert_eq!(
+			Weekday::try_from("thu"),
+			Ok(Weekday::Thursday)
+		);
+		assert!(
+			Weekday::try_from("th")
+			.is_err()
+		);
+	}
+
+	// test easter
+	#[test]
+	fn easter_known_dates() {
+		assert_eq!(
+			easter(2024),
+			Fixed::new(31,3,2024)
+		);
+		assert_eq!(
+			easter(2025),
+			Fixed::new(20,4,2025)
+		);
+		assert_eq!(
+			easter(2038),
+			Fixed::new(25,4,2038)
+		);
+		assert_eq!(
+			easter(2285),
+			Fixed::new(22,3,2285)
+		);
+	}
+	#[test]
+	fn try_new() {
+		assert_eq!(
+			Recurring::try_new(29,2),
+			Ok(Recurring::new(29,2))
+		);
+		assert_eq!(
+			Recurring::try_new(31,4),
+			Err(Error::OutOfRange{ what: "day out of range for month" })
+		);
+		assert_eq!(
+			Recurring::try_new(1,13),
+			Err(Error::OutOfRange{ what: "month out of range" })
+		);
+		assert_eq!(
+			Recurring::try_new(0,1),
+			Err(Error::OutOfRange{ what: "day out of range for month" })
+		);
+		assert_eq!(
+			Fixed::try_new(29,2,2024),
+			Ok(Fixed::new(29,2,2024))
+		);
+		assert_eq!(
+			Fixed::try_new(29,2,2023),
+			Err(Error::OutOfRange{ what: "day out of range for month" })
+		);
+		assert_eq!(
+			Fixed::try_new(31,2,2023),
+			Err(Error::OutOfRange{ what: "day out of range for month" })
+		);
+		assert_eq!(
+			Fixed::try_new(1,0,2023),
+			Err(Error::OutOfRange{ what: "month out of range" })
+		);
+	}
+	#[test]
+	fn next_at_max_year() {
+		let last = Fixed::new(31,12,i32::MAX);
+		assert_eq!(
+			last.next(),
+			last
+		);
+		assert_eq!(
+			last.checked_next(),
+			None
+		);
+		assert_eq!(
+			Fixed::new(30,12,i32::MAX).checked_next(),
+			Some(last)
+		);
+		assert_eq!(
+			dates_in_range(Fixed::new(30,12,i32::MAX), last).count(),
+			2
+		);
+	}
+	#[test]
+	fn prev_at_min_year() {
+		let first = Fixed::new(1,1,i32::MIN);
+		assert_eq!(
+			first.prev(),
+			first
+		);
+		assert_eq!(
+			first.checked_prev(),
+			None
+		);
+		assert_eq!(
+			Fixed::new(2,1,i32::MIN).checked_prev(),
+			Some(first)
+		);
+		assert_eq!(
+			Fixed::new(31,12,2000).checked_prev_match_on(Fixed::new(1,6,i32::MIN), LeapDayPolicy::default()),
+			None
+		);
+		assert_eq!(
+			Fixed::new(31,12,2000).prev_match_on(Fixed::new(1,6,i32::MIN), LeapDayPolicy::default()),
+			Fixed::new(1,6,i32::MIN)
+		);
+		assert_eq!(
+			Fixed::new(1,1,2000).prev_match_on(Fixed::new(1,6,i32::MIN), LeapDayPolicy::default()),
+			Fixed::new(1,1,i32::MIN)
+		);
+	}
+	#[test]
+	fn dates_in_range_count() {
+		assert_eq!(
+			dates_in_range(Fixed::new(25,1,2023), Fixed::new(5,2,2023)).count(),
+			12
+		);
+		assert_eq!(
+			dates_in_range(Fixed::new(1,2,2024), Fixed::new(1,3,2024)).count(),
+			30
+		);
+		assert_eq!(
+			dates_in_range(Fixed::new(28,2,2024), Fixed::new(1,3,2024)).collect::<Vec<_>>(),
+			vec![Fixed::new(28,2,2024), Fixed::new(29,2,2024), Fixed::new(1,3,2024)]
+		);
+		assert_eq!(
+			dates_in_range(Fixed::new(2,3,2024), Fixed::new(1,3,2024)).count(),
+			0
+		);
+	}
+	#[test]
+	fn add_days() {
+		assert_eq!(
+			Fixed::new(31,3,2024).add_days(-2),
+			Fixed::new(29,3,2024)
+		);
+		assert_eq!(
+			Fixed::new(31,3,2024).add_days(50),
+			Fixed::new(20,5,2024)
+		);
+		assert_eq!(
+			Fixed::new(31,3,2024).add_days(0),
+			Fixed::new(31,3,2024)
+		);
+		assert_eq!(
+			Fixed::new(1,3,0).add_days(-1),
+			Fixed::new(29,2,0)
+		);
+	}
+	#[test]
+	fn add_days_round_trip() {
+		let origin = Fixed::new(1,1,1970);
+		for days in [-800000, -146097, -1, 0, 59, 365, 146097, 800000] {
+			assert_eq!(
+				origin.signed_to(origin.add_days(days)),
+				days as i64
+			);
+		}
+		assert_eq!(
+			Fixed::new(31,12,Year::MAX).add_days(1),
+			Fixed::new(31,12,Year::MAX)
+		);
+		assert_eq!(
+			Fixed::new(1,1,Year::MIN).add_days(-1),
+			Fixed::new(1,1,Year::MIN)
+		);
+	}
+
+	// test to_rce
+	#[test]
+	fn to_rce_round_trip() {
+		for date in [Fixed::new(1,2,3), Fixed::new(29,2,2024), Fixed::new(31,12,-44)] {
+			assert_eq!(
+				Fixed::try_from(date.to_rce().as_str()).unwrap(),
+				date
+			);
+		}
+		assert_eq!(
+			Recurring::new(9,4).to_rce(),
+			"09,04"
+		);
+		assert_eq!(
+			AnyDate::Fixed(Fixed::new(9,4,2023)).to_rce(),
+			"09,04,2023"
+		);
+	}
+
+	// test serde
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serde_round_trip() {
+		let recurring = Recurring::new(29,2);
+		let fixed = Fixed::new(31,12,-44);
+		for date in [AnyDate::Recurring(recurring), AnyDate::Fixed(fixed)] {
+			let json = serde_json::to_string(&date).unwrap();
+			assert_eq!(
+				serde_json::from_str::<AnyDate>(&json).unwrap(),
+				date
+			);
+		}
+		assert_eq!(
+			serde_json::to_string(&AnyDate::Recurring(recurring)).unwrap(),
+			r#"{"Recurring":{"month":2,"day":29}}"#
+		);
+		let json = serde_json::to_string(&recurring).unwrap();
+		assert_eq!(
+			serde_json::from_str::<Recurring>(&json).unwrap(),
+			recurring
+		);
+		let json = serde_json::to_string(&fixed).unwrap();
+		assert_eq!(
+			serde_json::from_str::<Fixed>(&json).unwrap(),
+			fixed
+		);
+	}
+
+	// test DateParser
+	#[test]
+	fn parser_month_names_dashes() {
+		let parser = DateParser::new().separator('-').month_names(true);
+		assert_eq!(
+			parser.parse_fixed("9-April-2023").unwrap(),
+			Fixed::new(9,4,2023)
+		);
+		assert_eq!(
+			parser.parse_recurring(" 25 - dec ").unwrap(),
+			Recurring::new(25,12)
+		);
+		assert_eq!(
+			parser.parse_any("9-4").unwrap(),
+			AnyDate::Recurring(Recurring::new(9,4))
+		);
+		assert!(
+			parser.parse_fixed("9,April,2023")
+			.is_err()
+		);
+	}
+	#[test]
+	fn parser_month_names_disabled() {
+		assert!(
+			DateParser::new().parse_fixed("9,April,2023")
+			.is_err()
+		);
+		assert!(
+			DateParser::new().month_names(true).parse_fixed("9,Ap,2023")
+			.is_err()
+		);
+	}
+	#[test]
+	fn parser_year_pivot() {
+		let parser = DateParser::new().year_pivot(Some(70));
+		assert_eq!(
+			parser.parse_fixed("1,1,99").unwrap(),
+			Fixed::new(1,1,1999)
+		);
+		assert_eq!(
+			DateParser::new().parse_fixed("1,1,99").unwrap(),
+			Fixed::new(1,1,99)
+		);
+	}
+
 	// test impl Fixed
 	#[test]
 	fn next_day() {
@@ -446,6 +1869,286 @@ mod test {
 		);
 	}
 	#[test]
+	fn prev_day() {
+		assert_eq!(
+			Fixed::new(2, 1, 1970).prev(),
+			Fixed::new(1, 1, 1970)
+		);
+	}
+	#[test]
+	fn prev_month_february_leap() {
+		assert_eq!(
+			Fixed::new(1, 3, 2000).prev(),
+			Fixed::new(29, 2, 2000)
+		);
+	}
+	#[test]
+	fn prev_year() {
+		assert_eq!(
+			Fixed::new(1, 1, 1971).prev(),
+			Fixed::new(31, 12, 1970)
+		);
+	}
+	#[test]
+	fn clamp_leap_day_not_leap() {
+		assert_eq!(
+			Fixed::new(29, 2, 2023).clamp_leap_day(),
+			Fixed::new(28, 2, 2023)
+		);
+	}
+	#[test]
+	fn clamp_leap_day_leap() {
+		assert_eq!(
+			Fixed::new(29, 2, 2024).clamp_leap_day(),
+			Fixed::new(29, 2, 2024)
+		);
+		assert_eq!(
+			Fixed::new(1, 3, 2023).clamp_leap_day(),
+			Fixed::new(1, 3, 2023)
+		);
+	}
+	#[test]
+	fn days_in_year_leap() {
+		assert_eq!(
+			Fixed::new(1, 6, 2024).days_in_year(),
+			366
+		);
+		assert_eq!(
+			Fixed::new(31, 12, 2024).day_of_year(),
+			366
+		);
+	}
+	#[test]
+	fn days_in_year_not_leap() {
+		assert_eq!(
+			Fixed::new(1, 6, 2023).days_in_year(),
+			365
+		);
+		assert_eq!(
+			Fixed::new(1, 3, 2023).day_of_year(),
+			60
+		);
+	}
+	#[test]
+	fn day_of_year_boundary() {
+		assert_eq!(
+			Fixed::new(1, 1, 2023).day_of_year(),
+			1
+		);
+		assert_eq!(
+			Fixed::new(31, 12, 2023).day_of_year(),
+			365
+		);
+		assert_eq!(
+			Fixed::new(1, 3, 2024).day_of_year(),
+			61
+		);
+	}
+	#[test]
+	fn weekday_known_dates() {
+		assert_eq!(
+			Fixed::new(1, 1, 1970).weekday(),
+			Weekday::Thursday
+		);
+		assert_eq!(
+			Fixed::new(29, 2, 2000).weekday(),
+			Weekday::Tuesday
+		);
+		assert_eq!(
+			Fixed::new(25, 12, 2024).weekday(),
+			Weekday::Wednesday
+		);
+	}
+	// the day counting loop replaced by Fixed::ordinal
+	fn to_loop(from: Fixed, target: Fixed) -> u32 {
+		let mut current = from;
+		let mut count: u32 = 0;
+		while current < target {
+			current = current.next();
+			count = count.checked_add(1).unwrap();
+		}
+		count
+	}
+
+	#[test]
+	fn bc_boundary() {
+		// 31/12/1 BC is followed by 01/01/1 AD
+		assert_eq!(
+			Fixed::new(31,12,0).next(),
+			Fixed::new(1,1,1)
+		);
+		assert_eq!(
+			Fixed::new(1,1,1).prev(),
+			Fixed::new(31,12,0)
+		);
+		assert_eq!(
+			Fixed::new(31,12,-1).next(),
+			Fixed::new(1,1,0)
+		);
+		assert!(is_leap(0));
+		assert!(is_leap(-4));
+		assert!(!is_leap(-1));
+		assert!(!is_leap(-100));
+		assert!(is_leap(-400));
+		assert_eq!(
+			Fixed::new(1,1,0).to(Fixed::new(1,1,1)),
+			366
+		);
+		assert_eq!(
+			Fixed::new(28,2,0).next(),
+			Fixed::new(29,2,0)
+		);
+		assert_eq!(
+			Fixed::new(1,1,1).weekday(),
+			Weekday::Monday
+		);
+		assert_eq!(
+			Fixed::new(1,1,0).weekday(),
+			Weekday::Saturday
+		);
+		assert_eq!(
+			Fixed::try_from("29,2,-4").unwrap(),
+			Fixed::new(29,2,-4)
+		);
+		assert!(
+			Fixed::try_from("29,2,-1")
+			.is_err()
+		);
+		assert_eq!(
+			Fixed::new(1,3,-1).day_of_year(),
+			60
+		);
+	}
+	#[test]
+	fn years_until_next_match() {
+		let now = Fixed::now();
+		let today_born = Fixed::new(now.date.day, now.date.month, 1990).clamp_leap_day();
+		if today_born.date == now.date {
+			assert_eq!(
+				today_born.years_until_next_match(),
+				Ok(now.year - 1990)
+			);
+		}
+		let born = Fixed::new(1, 1, 1990);
+		let next = born.next_match();
+		assert_eq!(
+			born.years_until_next_match(),
+			Ok(next.year - 1990)
+		);
+		assert!(next >= now);
+	}
+	#[test]
+	fn next_anniversary_leap_day() {
+		let born = Fixed::new(29,2,2000);
+		for (today, expected) in [
+			(Fixed::new(27,2,2023), (Fixed::new(28,2,2023), 23)),
+			(Fixed::new(28,2,2023), (Fixed::new(28,2,2023), 23)),
+			(Fixed::new(1,3,2023), (Fixed::new(29,2,2024), 24)),
+			(Fixed::new(28,2,2024), (Fixed::new(29,2,2024), 24)),
+			(Fixed::new(29,2,2024), (Fixed::new(29,2,2024), 24)),
+			(Fixed::new(1,3,2024), (Fixed::new(28,2,2025), 25)),
+		] {
+			assert_eq!(
+				born.next_anniversary_on(today, LeapDayPolicy::Feb28),
+				Ok(expected)
+			);
+		}
+	}
+	#[test]
+	fn next_anniversary_extreme_years() {
+		let today = Fixed::new(1,6,2024);
+		assert_eq!(
+			Fixed::new(1,1,i32::MIN).next_anniversary_on(today, LeapDayPolicy::Feb28),
+			Err(Error::OutOfRange{ what: "years apart out of range" })
+		);
+		assert_eq!(
+			Fixed::new(1,1,i32::MIN).year_diff(Fixed::new(1,1,i32::MAX)),
+			Err(Error::OutOfRange{ what: "years apart out of range" })
+		);
+		assert_eq!(
+			Fixed::new(1,1,i32::MAX).year_diff(Fixed::new(1,1,-1)),
+			Ok(i32::MIN)
+		);
+		// no leap year is left after i32::MAX - 3
+		let last_leap = Fixed::new(1,3,i32::MAX - 3);
+		assert_eq!(
+			Fixed::new(29,2,2000).checked_next_match_on(last_leap, LeapDayPolicy::Skip),
+			None
+		);
+		assert_eq!(
+			Fixed::new(29,2,2000).next_match_on(last_leap, LeapDayPolicy::Skip),
+			last_leap
+		);
+		assert_eq!(
+			Fixed::new(29,2,2000).next_anniversary_on(last_leap, LeapDayPolicy::Skip),
+			Err(Error::OutOfRange{ what: "no next match in the representable years" })
+		);
+	}
+	#[test]
+	fn signed_to_both_directions() {
+		let a = Fixed::new(25,12,2023);
+		let b = Fixed::new(1,3,2024);
+		assert_eq!(
+			a.signed_to(b),
+			67
+		);
+		assert_eq!(
+			b.signed_to(a),
+			-67
+		);
+		assert_eq!(
+			a.signed_to(a),
+			0
+		);
+		assert_eq!(
+			b.to(a),
+			0
+		);
+	}
+	#[test]
+	fn to_known() {
+		assert_eq!(
+			Fixed::new(28,2,1900).to(Fixed::new(1,3,1900)),
+			1
+		);
+		assert_eq!(
+			Fixed::new(28,2,2000).to(Fixed::new(1,3,2000)),
+			2
+		);
+		assert_eq!(
+			Fixed::new(1,1,1970).to(Fixed::new(1,1,1971)),
+			365
+		);
+		assert_eq!(
+			Fixed::new(1,1,1971).to(Fixed::new(1,1,1970)),
+			0
+		);
+	}
+	#[test]
+	fn to_matches_loop() {
+		// linear congruential generator, deterministic
+		let mut seed: u64 = 0x2545F4914F6CDD1D;
+		let mut random = |max: u32| -> u32 {
+			seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+			((seed >> 33) % max as u64) as u32
+		};
+		let mut random_date = || {
+			let year = 1700 + random(600) as i32;
+			let month = 1 + random(12);
+			let day = 1 + random(last_day(month, year));
+			Fixed::new(day, month, year)
+		};
+		for _ in 0..300 {
+			let from = random_date();
+			let target = random_date();
+			assert_eq!(
+				from.to(target),
+				to_loop(from, target),
+				"{} to {}", from, target
+			);
+		}
+	}
+	#[test]
 	fn next_month_february_not_leap() {
 		assert_eq!(
 			Fixed::new(28, 2, 1900).next(),
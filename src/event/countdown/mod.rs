@@ -0,0 +1,122 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Countdown {
+	desc: String,
+	date: date::Fixed,
+}
+
+impl TryFrom<&str> for Countdown {
+	type Error = Error;
+	fn try_from(value: &str) -> Result<Self> {
+		Countdown::parse(value, DEFAULT_SEPARATOR)
+	}
+}
+
+impl Countdown {
+
+	// same as try_from, the slots being separated by <separator>
+	pub fn parse(value: &str, separator: char) -> Result<Self> {
+		let mut iter = split_quoted(value, separator)?.into_iter();
+		let Some(desc) = iter.next() else {
+			return Err(Error::Parse{ what: "missing 'desc' slot" });
+		};
+		let Some(date) = iter.next() else {
+			return Err(Error::Parse{ what: "missing 'date' slot" });
+		};
+		if iter.next().is_some() {
+			return Err(Error::Parse{ what: "extra ';' found" });
+		};
+		let desc = unquote(desc);
+		if desc.trim().is_empty() {
+			return Err(Error::Parse{ what: "empty 'desc' slot" });
+		}
+		let desc = String::from(desc);
+		let date = date::Fixed::try_from(date)?;
+		Ok(Countdown{ desc, date })
+	}
+}
+
+impl fmt::Display for Countdown {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "countdown = {} ; {}", quote(&self.desc), self.date.to_rce())
+	}
+}
+
+impl IntoEvents for Countdown {
+	fn into_events_in(self, _context: &Context) -> Vec<Event> {
+		let event = Event {
+			kind: EventKind::Countdown,
+			date: self.date,
+			desc: self.desc,
+			recurring: None,
+			rule: None,
+			note: None,
+			lead: None,
+			tags: Vec::new(),
+		};
+		vec![event]
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn new_countdown() -> Countdown {
+		Countdown{ desc: String::from("thesis"), date: date::Fixed::new(30,6,2031) }
+	}
+
+	#[test]
+	fn parse() {
+		assert_eq!(
+			Countdown::try_from(" thesis ; 30,6,2031 ").unwrap(),
+			new_countdown()
+		);
+		assert!(
+			Countdown::try_from("thesis ; 30,6")
+			.is_err()
+		);
+		assert!(
+			Countdown::try_from(" ; 30,6,2031")
+			.is_err()
+		);
+		assert!(
+			Countdown::try_from("thesis ; 30,6,2031 ; 1,7,2031")
+			.is_err()
+		);
+	}
+	#[test]
+	fn display() {
+		assert_eq!(
+			new_countdown().to_string(),
+			"countdown = thesis ; 30,06,2031"
+		);
+	}
+	#[test]
+	fn into_events() {
+		let events = new_countdown().into_events();
+		assert_eq!(
+			events.len(),
+			1
+		);
+		assert!(events[0].kind == EventKind::Countdown);
+		assert_eq!(
+			events[0].date,
+			date::Fixed::new(30,6,2031)
+		);
+		assert_eq!(
+			events[0].desc,
+			"thesis"
+		);
+	}
+	#[test]
+	fn overdue_still_next() {
+		let past = Countdown{ desc: String::from("late"), date: date::Fixed::new(1,1,2000) };
+		let events = past.into_events();
+		assert_eq!(
+			get_next(&events, EventKind::Countdown).len(),
+			1
+		);
+	}
+} // mod test
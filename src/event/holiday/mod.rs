@@ -6,80 +6,301 @@ enum HolidayKind {
 	Recurring(date::Recurring),
 	Fixed(date::Fixed),
 	Span(date::Fixed, date::Fixed),
+	// comes back every year, end may be in the following year
+	RecurringSpan(date::Recurring, date::Recurring),
+	// moves with Easter Sunday, e.g. -2 for Good Friday
+	Easter { offset_days: i32 },
+	// the <nth> <weekday> of <month>, counted from the end if <nth> is negative
+	NthWeekday { month: u32, weekday: date::Weekday, nth: i32 },
+}
+
+// parse the value of a "nth,N,weekday,month" slot, e.g. "4,Thu,11" for the 4th Thursday of November
+fn parse_nth_weekday(value: Option<&str>) -> Result<HolidayKind> {
+	let Some(value) = value else {
+		return Err(Error::Parse{ what: "missing nth value" });
+	};
+	let slots: Vec<&str> = value.split(',').map(str::trim).collect();
+	let [nth, weekday, month] = slots[..] else {
+		return Err(Error::Parse{ what: "expected nth, N, weekday, month" });
+	};
+	let Ok(nth) = nth.parse::<i32>() else {
+		return Err(Error::Parse{ what: "failed to parse nth" });
+	};
+	if (nth == 0) || (nth.abs() > 5) {
+		return Err(Error::OutOfRange{ what: "nth must be in -5..=5 and not 0" });
+	}
+	let weekday = date::Weekday::try_from(weekday)?;
+	let Ok(month) = month.parse::<u32>() else {
+		return Err(Error::Parse{ what: "failed to parse month" });
+	};
+	if !(1..=12).contains(&month) {
+		return Err(Error::OutOfRange{ what: "month out of range" });
+	}
+	Ok(HolidayKind::NthWeekday{ month, weekday, nth })
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Holiday {
 	desc: String,
 	kind: HolidayKind,
+	// see Event::lead
+	lead: Option<u32>,
 }
 
 impl TryFrom<&str> for Holiday {
 	type Error = Error;
 	fn try_from(value: &str) -> Result<Self> {
-		let mut iter = value.split(';');
+		Holiday::parse(value, DEFAULT_SEPARATOR)
+	}
+}
+
+impl Holiday {
+
+	// same as try_from, the slots being separated by <separator>
+	pub fn parse(value: &str, separator: char) -> Result<Self> {
+		let mut iter = split_quoted(value, separator)?.into_iter();
 		let Some(desc) = iter.next() else {
-			return Err("missing 'desc' slot");
+			return Err(Error::Parse{ what: "missing 'desc' slot" });
 		};
 		let Some(begin) = iter.next() else {
-			return Err("missing 'begin' slot");
-		};
-		let end = iter.next();
-		if iter.next().is_some() {
-			return Err("extra ';' found");
+			return Err(Error::Parse{ what: "missing 'begin' slot" });
 		};
-		let desc = String::from(desc.trim());
+		let mut end = None;
+		let mut lead = None;
+		for slot in iter {
+			match parse_option(slot) {
+				("lead", value) => lead = Some(parse_lead(value)?),
+				_ if end.is_none() && lead.is_none() => end = Some(slot),
+				_ => return Err(Error::Parse{ what: "extra ';' found" }),
+			}
+		}
+		let desc = unquote(desc);
+		if desc.trim().is_empty() {
+			return Err(Error::Parse{ what: "empty 'desc' slot" });
+		}
+		let desc = String::from(desc);
 		if let Some(end) = end {
+			if let (Ok(begin), Ok(end)) = (date::Recurring::try_from(begin), date::Recurring::try_from(end)) {
+				return match begin == end {
+					false => Ok(Holiday{ desc, kind: HolidayKind::RecurringSpan(begin, end), lead }),
+					true => Ok(Holiday{ desc, kind: HolidayKind::Recurring(begin), lead }),
+				};
+			}
 			let begin = date::Fixed::try_from(begin)?;
 			let end = date::Fixed::try_from(end)?;
 			return match begin.cmp(&end) {
-				Ordering::Less => Ok(Holiday{ desc, kind: HolidayKind::Span(begin, end) }),
-				Ordering::Equal => Ok(Holiday{ desc, kind: HolidayKind::Fixed(begin) }),
-				Ordering::Greater => Err("begin is after end"),
+				Ordering::Less => Ok(Holiday{ desc, kind: HolidayKind::Span(begin, end), lead }),
+				Ordering::Equal => Ok(Holiday{ desc, kind: HolidayKind::Fixed(begin), lead }),
+				Ordering::Greater => Err(Error::OutOfRange{ what: "begin is after end" }),
+			};
+		}
+		if let ("nth", value) = parse_option(begin) {
+			return Ok(Holiday{ desc, kind: parse_nth_weekday(value)?, lead });
+		}
+		if let ("easter", offset) = parse_option(begin) {
+			let offset_days = match offset {
+				None => 0,
+				Some(offset) => match offset.parse::<i32>() {
+					Ok(offset) => offset,
+					Err(_) => return Err(Error::Parse{ what: "failed to parse easter offset" }),
+				},
 			};
+			return Ok(Holiday{ desc, kind: HolidayKind::Easter{ offset_days }, lead });
 		}
 		if let Ok(begin) = date::Recurring::try_from(begin) {
-			return Ok(Holiday{ desc, kind: HolidayKind::Recurring(begin) });
+			return Ok(Holiday{ desc, kind: HolidayKind::Recurring(begin), lead });
 		}
 		if let Ok(begin) = date::Fixed::try_from(begin) {
-			return Ok(Holiday{ desc, kind: HolidayKind::Fixed(begin) });
+			return Ok(Holiday{ desc, kind: HolidayKind::Fixed(begin), lead });
 		}
-		Err("no Holiday format matched")
+		Err(Error::Parse{ what: "no Holiday format matched" })
 	}
 }
 
-impl IntoEvents for Holiday {
-	fn into_events(self) -> Vec<Event> {
+impl fmt::Display for Holiday {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "holiday = {} ; ", quote(&self.desc))?;
+		match self.kind {
+			HolidayKind::Recurring(recurring) => write!(f, "{}", recurring.to_rce())?,
+			HolidayKind::Fixed(fixed) => write!(f, "{}", fixed.to_rce())?,
+			HolidayKind::Span(begin, end) => write!(f, "{} ; {}", begin.to_rce(), end.to_rce())?,
+			HolidayKind::RecurringSpan(begin, end) => write!(f, "{} ; {}", begin.to_rce(), end.to_rce())?,
+			HolidayKind::Easter{ offset_days: 0 } => write!(f, "easter")?,
+			HolidayKind::Easter{ offset_days } => write!(f, "easter, {}", offset_days)?,
+			HolidayKind::NthWeekday{ month, weekday, nth } => write!(f, "nth, {}, {}, {}", nth, &weekday.to_string()[..3], month)?,
+		};
+		if let Some(lead) = self.lead {
+			write!(f, " ; lead, {}", lead)?;
+		}
+		Ok(())
+	}
+}
+
+// return the first occurrence of the yearly span [begin, end] not over at <now>
+// the span wraps to the following year if end is before begin
+fn resolve_span(begin: date::Recurring, end: date::Recurring, now: date::Fixed) -> (date::Fixed, date::Fixed) {
+	let wrap = if end < begin { 1 } else { 0 };
+	let mut year = now.year() - wrap;
+	loop {
+		let span = (begin.in_year(year), end.in_year(year + wrap));
+		if span.1 >= now {
+			break span;
+		}
+		year += 1;
+	}
+}
+
+// return one event per day of [begin, end] counting the days remaining
+// each event recurs on its own day & month if <recurring>
+fn span_events(desc: &str, begin: date::Fixed, end: date::Fixed, recurring: bool, lead: Option<u32>) -> Vec<Event> {
+	let mut vec: Vec<Event> = Vec::new();
+	let mut remaining = begin.to(end)+1;
+	for current in date::dates_in_range(begin, end) {
+		remaining = remaining.checked_sub(1).unwrap();
+		let event = Event {
+			kind: EventKind::Holiday,
+			date: current,
+			desc: format!("{} ({} days remaining)", desc, remaining),
+			recurring: if recurring { Some(current.recurring()) } else { None },
+			rule: None,
+			note: None,
+			lead,
+			tags: Vec::new(),
+		};
+		vec.push(event);
+	}
+	vec
+}
+
+// return the summary event of the span [begin, end], e.g. "Summer (62 days)"
+// dated today if the span is in progress, on its first day otherwise
+fn span_summary(desc: &str, begin: date::Fixed, end: date::Fixed, recurring: bool, lead: Option<u32>, now: date::Fixed) -> Event {
+	let date = if (begin < now) && (now <= end) { now } else { begin };
+	Event {
+		kind: EventKind::Holiday,
+		date,
+		desc: format!("{} ({} days)", desc, begin.to(end)+1),
+		recurring: if recurring { Some(begin.recurring()) } else { None },
+		rule: None,
+		note: None,
+		lead,
+		tags: Vec::new(),
+	}
+}
+
+impl Holiday {
+
+	// number of days covered, both ends included
+	// None for a single day coming back every year
+	pub fn duration_days(&self) -> Option<u32> {
+		self.duration_days_on(date::Fixed::now())
+	}
+
+	// same as duration_days, a span coming back every year measured on its occurrence not over at <today>
+	pub fn duration_days_on(&self, today: date::Fixed) -> Option<u32> {
+		match self.kind {
+			HolidayKind::Span(begin, end) => Some(begin.to(end) + 1),
+			HolidayKind::RecurringSpan(begin, end) => {
+				let (begin, end) = resolve_span(begin, end, today);
+				Some(begin.to(end) + 1)
+			},
+			HolidayKind::Fixed(_) => Some(1),
+			HolidayKind::Recurring(_) => None,
+			HolidayKind::Easter{ .. } => None,
+			HolidayKind::NthWeekday{ .. } => None,
+		}
+	}
+
+	// merge the overlapping or adjacent spans sharing a description into a single span,
+	// other holidays are kept in their original order, followed by the merged spans
+	pub fn merge_spans(holidays: Vec<Holiday>) -> Vec<Holiday> {
+		let (mut spans, mut merged): (Vec<Holiday>, Vec<Holiday>) = holidays
+			.into_iter()
+			.partition(|holiday| matches!(holiday.kind, HolidayKind::Span(..)));
+		spans.sort_by(|lhs, rhs| match (&lhs.kind, &rhs.kind) {
+			(HolidayKind::Span(lhs_begin, _), HolidayKind::Span(rhs_begin, _)) => {
+				lhs.desc.cmp(&rhs.desc).then(lhs_begin.cmp(rhs_begin))
+			},
+			_ => Ordering::Equal,
+		});
+		let mut current: Option<Holiday> = None;
+		for span in spans {
+			let HolidayKind::Span(begin, end) = span.kind else { continue };
+			if let Some(Holiday{ desc, kind: HolidayKind::Span(_, ref mut last_end), lead }) = current.as_mut() {
+				if (*desc == span.desc) && (begin <= last_end.next()) {
+					*last_end = (*last_end).max(end);
+					*lead = (*lead).max(span.lead);
+					continue;
+				}
+			}
+			merged.extend(current.replace(span));
+		}
+		merged.extend(current);
+		merged
+	}
+
+	// return the event of the first occurrence of <rule> on or after <today>, if any
+	fn moving_event(&self, rule: date::Rule, today: date::Fixed) -> Option<Event> {
+		let date = rule.next_on_or_after(today)?;
+		Some(Event {
+			kind: EventKind::Holiday,
+			date,
+			desc: self.desc.clone(),
+			recurring: None,
+			rule: Some(rule),
+			note: None,
+			lead: self.lead,
+			tags: Vec::new(),
+		})
+	}
+
+	// same as into_events_in, without consuming the holiday
+	pub fn into_events_with(&self, context: &Context) -> Vec<Event> {
 		let mut vec: Vec<Event> = Vec::new();
 		match self.kind {
 			HolidayKind::Recurring(recurring) => {
 				let event = Event {
 					kind: EventKind::Holiday,
-					date: date::Fixed::from(recurring).next_match(),
-					desc: self.desc,
+					date: date::Fixed::from(recurring).next_match_on(context.today, context.leap_day),
+					desc: self.desc.clone(),
+					recurring: Some(recurring),
+					rule: None,
+					note: None,
+					lead: self.lead,
+					tags: Vec::new(),
 				};
 				vec.push(event);
 			},
 			HolidayKind::Fixed(fixed) => {
 				let event = Event {
 					kind: EventKind::Holiday,
-					date: fixed.next_match(),
-					desc: self.desc,
+					date: fixed.next_match_on(context.today, context.leap_day),
+					desc: self.desc.clone(),
+					recurring: Some(fixed.recurring()),
+					rule: None,
+					note: None,
+					lead: self.lead,
+					tags: Vec::new(),
 				};
 				vec.push(event);
 			},
+			HolidayKind::Easter{ offset_days } => {
+				vec.extend(self.moving_event(date::Rule::Easter{ offset_days }, context.today));
+			},
+			HolidayKind::NthWeekday{ month, weekday, nth } => {
+				vec.extend(self.moving_event(date::Rule::NthWeekday{ month, weekday, nth }, context.today));
+			},
+			HolidayKind::Span(begin, end) if context.expand_spans => {
+				vec = span_events(&self.desc, begin, end, false, self.lead);
+			},
 			HolidayKind::Span(begin, end) => {
-				let mut remaining = begin.to(end)+1;
-				let mut current = begin;
-				while current <= end {
-					remaining = remaining.checked_sub(1).unwrap();
-					let event = Event {
-						kind: EventKind::Holiday,
-						date: current,
-						desc: format!("{} ({} days remaining)", self.desc, remaining),
-					};
-					vec.push(event);
-					current = current.next();
+				vec.push(span_summary(&self.desc, begin, end, false, self.lead, context.today));
+			},
+			HolidayKind::RecurringSpan(begin, end) => {
+				let (begin, end) = resolve_span(begin, end, context.today);
+				match context.expand_spans {
+					true => vec = span_events(&self.desc, begin, end, true, self.lead),
+					false => vec.push(span_summary(&self.desc, begin, end, true, self.lead, context.today)),
 				}
 			},
 		};
@@ -87,6 +308,12 @@ impl IntoEvents for Holiday {
 	}
 }
 
+impl IntoEvents for Holiday {
+	fn into_events_in(self, context: &Context) -> Vec<Event> {
+		self.into_events_with(context)
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -95,6 +322,7 @@ mod test {
 		Holiday{
 			desc: String::from("Christmas"),
 			kind: HolidayKind::Recurring(date::Recurring::new(25,12)),
+			lead: None,
 		}
 	}
 
@@ -102,6 +330,7 @@ mod test {
 		Holiday{
 			desc: String::from("Easter"),
 			kind: HolidayKind::Fixed(date::Fixed::new(9,4,2023)),
+			lead: None,
 		}
 	}
 
@@ -111,10 +340,221 @@ mod test {
 			kind: HolidayKind::Span(
 				date::Fixed::new(1,7,2023),
 				date::Fixed::new(31,8,2023)
-			)
+			),
+			lead: None,
 		}
 	}
 
+	#[test]
+	fn holiday_parse_lead() {
+		assert_eq!(
+			Holiday::try_from("Christmas ; 25,12 ; lead, 7").unwrap(),
+			Holiday{ lead: Some(7), ..new_recurring() }
+		);
+		assert_eq!(
+			Holiday::try_from("Summer ; 1,7,2023 ; 31,8,2023 ; lead,7").unwrap(),
+			Holiday{ lead: Some(7), ..new_span() }
+		);
+		assert_eq!(
+			Holiday{ lead: Some(7), ..new_span() }.to_string(),
+			"holiday = Summer ; 01,07,2023 ; 31,08,2023 ; lead, 7"
+		);
+		assert!(
+			Holiday::try_from("Christmas ; 25,12 ; lead, 7 ; 26,12")
+			.is_err()
+		);
+		assert!(
+			Holiday::try_from("Christmas ; 25,12 ; lead")
+			.is_err()
+		);
+	}
+	#[test]
+	fn holiday_parse_recurring_span() {
+		let summer = Holiday::try_from("Summer ; 1,7 ; 31,8").unwrap();
+		assert_eq!(
+			summer.kind,
+			HolidayKind::RecurringSpan(date::Recurring::new(1,7), date::Recurring::new(31,8))
+		);
+		assert_eq!(
+			summer.to_string(),
+			"holiday = Summer ; 01,07 ; 31,08"
+		);
+		assert_eq!(
+			Holiday::try_from("Christmas ; 25,12 ; 25,12").unwrap(),
+			new_recurring()
+		);
+		assert!(
+			Holiday::try_from("Summer ; 1,7 ; 31,8,2023")
+			.is_err()
+		);
+	}
+	#[test]
+	fn holiday_resolve_recurring_span() {
+		let summer = (date::Recurring::new(1,7), date::Recurring::new(31,8));
+		assert_eq!(
+			resolve_span(summer.0, summer.1, date::Fixed::new(15,8,2030)),
+			(date::Fixed::new(1,7,2030), date::Fixed::new(31,8,2030))
+		);
+		assert_eq!(
+			resolve_span(summer.0, summer.1, date::Fixed::new(1,9,2030)),
+			(date::Fixed::new(1,7,2031), date::Fixed::new(31,8,2031))
+		);
+		let winter = (date::Recurring::new(20,12), date::Recurring::new(5,1));
+		assert_eq!(
+			resolve_span(winter.0, winter.1, date::Fixed::new(2,1,2030)),
+			(date::Fixed::new(20,12,2029), date::Fixed::new(5,1,2030))
+		);
+		assert_eq!(
+			resolve_span(winter.0, winter.1, date::Fixed::new(6,1,2030)),
+			(date::Fixed::new(20,12,2030), date::Fixed::new(5,1,2031))
+		);
+		assert_eq!(
+			resolve_span(winter.0, winter.1, date::Fixed::new(25,12,2030)),
+			(date::Fixed::new(20,12,2030), date::Fixed::new(5,1,2031))
+		);
+	}
+	#[test]
+	fn holiday_recurring_span_into_events() {
+		let events = Holiday::try_from("Winter ; 20,12 ; 5,1").unwrap().into_events();
+		assert_eq!(
+			events.len(),
+			17
+		);
+		assert!(events.windows(2).all(|pair| pair[0].date.next() == pair[1].date));
+		assert_eq!(
+			events[0].recurring,
+			Some(date::Recurring::new(20,12))
+		);
+		assert_eq!(
+			events[16].recurring,
+			Some(date::Recurring::new(5,1))
+		);
+		assert_eq!(
+			events[16].desc,
+			"Winter (0 days remaining)"
+		);
+		assert!(events[16].date >= date::Fixed::now());
+	}
+	#[test]
+	fn holiday_span_summary() {
+		let events = new_span().into_events_with(&Context{ expand_spans: false, ..Context::default() });
+		assert_eq!(
+			events.len(),
+			1
+		);
+		assert_eq!(
+			events[0].desc,
+			"Summer (62 days)"
+		);
+		assert_eq!(
+			events[0].date,
+			date::Fixed::new(1,7,2023)
+		);
+		let events = new_span().into_events_with(&Context::default());
+		assert_eq!(
+			events.len(),
+			62
+		);
+		assert_eq!(
+			events[0].desc,
+			"Summer (61 days remaining)"
+		);
+		assert_eq!(
+			new_span().into_events().len(),
+			62
+		);
+		let events = Holiday::try_from("Winter ; 20,12 ; 5,1").unwrap().into_events_with(&Context{ expand_spans: false, ..Context::default() });
+		assert_eq!(
+			events[0].desc,
+			"Winter (17 days)"
+		);
+		assert!(events[0].date >= date::Fixed::now());
+	}
+	#[test]
+	fn holiday_leap_day_policy() {
+		let context = Context{ leap_day: date::LeapDayPolicy::Skip, ..Context::default() };
+		let date = Holiday::try_from("Leap ; 29,2").unwrap().into_events_in(&context)[0].date;
+		assert_eq!(
+			(date.recurring(), date.days_in_year()),
+			(date::Recurring::new(29,2), 366)
+		);
+	}
+	#[test]
+	fn holiday_parse_easter() {
+		assert_eq!(
+			Holiday::try_from("Good Friday ; easter,-2").unwrap().kind,
+			HolidayKind::Easter{ offset_days: -2 }
+		);
+		assert_eq!(
+			Holiday::try_from("Easter ; easter").unwrap().kind,
+			HolidayKind::Easter{ offset_days: 0 }
+		);
+		assert_eq!(
+			Holiday::try_from("Pentecost ; easter, 49 ; lead, 3").unwrap().to_string(),
+			"holiday = Pentecost ; easter, 49 ; lead, 3"
+		);
+		assert!(
+			Holiday::try_from("Good Friday ; easter, soon")
+			.is_err()
+		);
+	}
+	#[test]
+	fn holiday_easter_into_events() {
+		let events = Holiday::try_from("Good Friday ; easter,-2").unwrap().into_events();
+		let now = date::Fixed::now();
+		let date = events[0].date;
+		assert!(date >= now);
+		assert!(
+			(date == date::easter(now.year()).add_days(-2)) ||
+			(date == date::easter(now.year() + 1).add_days(-2))
+		);
+		assert_eq!(
+			date.weekday(),
+			date::Weekday::Friday
+		);
+	}
+	#[test]
+	fn holiday_nth_weekday() {
+		let thanksgiving = Holiday::try_from("Thanksgiving ; nth,4,Thu,11").unwrap();
+		assert_eq!(
+			thanksgiving.kind,
+			HolidayKind::NthWeekday{ month: 11, weekday: date::Weekday::Thursday, nth: 4 }
+		);
+		assert_eq!(
+			thanksgiving.to_string(),
+			"holiday = Thanksgiving ; nth, 4, Thu, 11"
+		);
+		let memorial = Holiday::try_from("Memorial Day ; nth, -1, monday, 5").unwrap();
+		let context = Context{ today: date::Fixed::new(1,1,2024), ..Context::default() };
+		let thanksgiving = thanksgiving.into_events_in(&context);
+		let memorial = memorial.into_events_in(&context);
+		let context = Context{ today: date::Fixed::new(29,11,2024), ..Context::default() };
+		let next = Holiday::try_from("Thanksgiving ; nth,4,Thu,11").unwrap().into_events_in(&context);
+		assert_eq!(
+			thanksgiving[0].date,
+			date::Fixed::new(28,11,2024)
+		);
+		assert_eq!(
+			memorial[0].date,
+			date::Fixed::new(27,5,2024)
+		);
+		assert_eq!(
+			next[0].date,
+			date::Fixed::new(27,11,2025)
+		);
+		assert!(
+			Holiday::try_from("Nope ; nth,0,Thu,11")
+			.is_err()
+		);
+		assert!(
+			Holiday::try_from("Nope ; nth,4,Thu")
+			.is_err()
+		);
+		assert!(
+			Holiday::try_from("Nope ; nth,4,Thu,13")
+			.is_err()
+		);
+	}
 	#[test]
 	fn holiday_parse_recurring() {
 		assert_eq!(
@@ -152,6 +592,28 @@ mod test {
 		);
 	}
 	#[test]
+	fn holiday_parse_empty_desc() {
+		assert!(
+			Holiday::try_from(" \t ;25,12")
+			.is_err()
+		);
+	}
+	#[test]
+	fn holiday_display() {
+		assert_eq!(
+			new_recurring().to_string(),
+			"holiday = Christmas ; 25,12"
+		);
+		assert_eq!(
+			new_fixed().to_string(),
+			"holiday = Easter ; 09,04,2023"
+		);
+		assert_eq!(
+			new_span().to_string(),
+			"holiday = Summer ; 01,07,2023 ; 31,08,2023"
+		);
+	}
+	#[test]
 	fn holiday_parse_span() {
 		assert_eq!(
 			Holiday::try_from("Summer;1,7,2023;  31 ,8  ,    2023").unwrap(),
@@ -173,4 +635,86 @@ mod test {
 			new_span()
 		);
 	}
+	#[test]
+	fn holiday_merge_spans_overlapping() {
+		let holidays = vec![
+			Holiday::try_from("Summer ; 1,8,2023 ; 15,9,2023").unwrap(),
+			new_recurring(),
+			new_span(),
+		];
+		assert_eq!(
+			Holiday::merge_spans(holidays),
+			vec![
+				new_recurring(),
+				Holiday::try_from("Summer ; 1,7,2023 ; 15,9,2023").unwrap(),
+			]
+		);
+	}
+	#[test]
+	fn holiday_merge_spans_adjacent() {
+		let holidays = vec![
+			new_span(),
+			Holiday::try_from("Summer ; 1,9,2023 ; 10,9,2023").unwrap(),
+		];
+		assert_eq!(
+			Holiday::merge_spans(holidays),
+			vec![Holiday::try_from("Summer ; 1,7,2023 ; 10,9,2023").unwrap()]
+		);
+	}
+	#[test]
+	fn holiday_merge_spans_disjoint() {
+		let holidays = vec![
+			Holiday::try_from("Summer ; 2,9,2023 ; 10,9,2023").unwrap(),
+			new_span(),
+			Holiday::try_from("Winter ; 1,8,2023 ; 10,8,2023").unwrap(),
+		];
+		assert_eq!(
+			Holiday::merge_spans(holidays),
+			vec![
+				new_span(),
+				Holiday::try_from("Summer ; 2,9,2023 ; 10,9,2023").unwrap(),
+				Holiday::try_from("Winter ; 1,8,2023 ; 10,8,2023").unwrap(),
+			]
+		);
+	}
+	#[test]
+	fn holiday_duration_days() {
+		assert_eq!(
+			new_span().duration_days(),
+			Some(62)
+		);
+		assert_eq!(
+			new_fixed().duration_days(),
+			Some(1)
+		);
+		assert_eq!(
+			new_recurring().duration_days(),
+			None
+		);
+		assert_eq!(
+			Holiday::try_from("Good Friday ; easter,-2").unwrap().duration_days(),
+			None
+		);
+		assert_eq!(
+			Holiday::try_from("Thanksgiving ; nth,4,Thu,11").unwrap().duration_days(),
+			None
+		);
+	}
+	#[test]
+	fn holiday_duration_days_on() {
+		let winter = Holiday::try_from("Winter ; 20,2 ; 5,3").unwrap();
+		// 29/02 is only spanned in leap years
+		assert_eq!(
+			winter.duration_days_on(date::Fixed::new(1,1,2023)),
+			Some(14)
+		);
+		assert_eq!(
+			winter.duration_days_on(date::Fixed::new(1,1,2024)),
+			Some(15)
+		);
+		assert_eq!(
+			winter.duration_days_on(date::Fixed::new(6,3,2023)),
+			Some(15)
+		);
+	}
 } // mod test
@@ -1,72 +1,353 @@
 use super::*;
 use colored::*;
-use std::{fmt, cmp};
+use unicode_width::UnicodeWidthStr;
+use std::{fmt, fs, cmp, collections::{BTreeMap, HashMap, HashSet}, io::BufRead, panic, path::{Path, PathBuf}, sync::OnceLock};
 
 pub mod person;
 pub mod holiday;
 pub mod special;
+pub mod countdown;
+pub mod weekly;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(feature = "sqlite")]
+pub use sqlite::load_from_sqlite;
 
-#[derive(PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum EventKind {
 	Birthday,
 	SaintDay,
 	Wedding,
 	Holiday,
 	Special,
+	Countdown,
+	Weekly,
+}
+
+impl EventKind {
+
+	// return the uncolored name
+	pub fn name(self) -> &'static str {
+		match self {
+			EventKind::Birthday => "birthday",
+			EventKind::SaintDay => "saint day",
+			EventKind::Wedding => "wedding anniversary",
+			EventKind::Holiday => "holiday",
+			EventKind::Special => "special",
+			EventKind::Countdown => "countdown",
+			EventKind::Weekly => "weekly",
+		}
+	}
+}
+
+// return the error message listing the names of KIND_LIST, built on first use
+fn no_kind_matched() -> &'static str {
+	static WHAT: OnceLock<String> = OnceLock::new();
+	WHAT.get_or_init(|| {
+		let names: Vec<&str> = KIND_LIST.iter().map(|kind| kind.name()).collect();
+		format!("no EventKind matched, expected one of: {}", names.join(", "))
+	})
+}
+
+// parse a kind from its name, case-insensitive, '-' or '_' may replace the spaces
+// and several spaces count as one
+impl TryFrom<&str> for EventKind {
+	type Error = Error;
+	fn try_from(value: &str) -> Result<Self> {
+		let name = value.to_lowercase().replace(['-', '_'], " ");
+		let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+		let Some(kind) = KIND_LIST.into_iter().find(|kind| kind.name() == name) else {
+			return Err(Error::Parse{ what: no_kind_matched() });
+		};
+		Ok(kind)
+	}
 }
 
 impl fmt::Display for EventKind {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let name = self.name();
 		let text = match self {
-			EventKind::Birthday => "birthday".red(),
-			EventKind::SaintDay => "saint day".blue(),
-			EventKind::Wedding => "wedding anniversary".green(),
-			EventKind::Holiday => "holiday".yellow(),
-			EventKind::Special => "special".cyan(),
+			EventKind::Birthday => name.red(),
+			EventKind::SaintDay => name.blue(),
+			EventKind::Wedding => name.green(),
+			EventKind::Holiday => name.yellow(),
+			EventKind::Special => name.cyan(),
+			EventKind::Countdown => name.magenta(),
+			EventKind::Weekly => name.white(),
 		};
 		write!(f, "{}", text)
 	}
 }
 
-pub const KIND_LIST: [EventKind; 5] = [
+pub const KIND_LIST: [EventKind; 7] = [
 	EventKind::Birthday,
 	EventKind::SaintDay,
 	EventKind::Wedding,
 	EventKind::Holiday,
 	EventKind::Special,
+	EventKind::Countdown,
+	EventKind::Weekly,
 ];
 
+#[derive(Debug, Clone)]
 pub struct Event {
 	pub kind: EventKind,
 	pub date: date::Fixed,
 	pub desc: String,
+	// day & month the event comes back on every year, None for one-off events
+	pub recurring: Option<date::Recurring>,
+	// how the event comes back when its day & month are not enough, e.g. Easter
+	pub rule: Option<date::Rule>,
+	// personal note, only shown on demand
+	pub note: Option<String>,
+	// number of days before the date the event starts being announced
+	pub lead: Option<u32>,
+	// categories the event can be filtered on, e.g. "work"
+	pub tags: Vec<String>,
 }
 
-pub trait IntoEvents {
-	fn into_events(self) -> Vec<Event>;
+impl Event {
+
+	// return an identifier, equal for events of same kind, date & description
+	pub fn uid(&self) -> String {
+		format!("{}|{}|{}", self.kind.name(), self.date, self.desc)
+	}
+
+	// return the description, followed by the note if requested
+	pub fn full_desc(&self, with_note: bool) -> String {
+		match &self.note {
+			Some(note) if with_note => format!("{} [{}]", self.desc, note),
+			_ => self.desc.clone(),
+		}
+	}
+
+	// return the number of terminal columns taken by the description
+	// accented letters take 1 column and CJK characters 2, whatever their length in bytes
+	pub fn display_width(&self) -> usize {
+		self.desc.width()
+	}
+
+	// suggest a day off between a holiday and the week-end
+	pub fn bridge_note(&self) -> Option<String> {
+		if self.kind != EventKind::Holiday {
+			return None;
+		}
+		let bridge = self.date.bridge_day()?;
+		Some(format!("bridge: take {} {} off", bridge.weekday(), bridge.recurring()))
+	}
+
+	// return what the events are ordered by: date, kind as in KIND_LIST, description
+	fn sort_key(&self) -> (date::Fixed, Option<usize>, &str) {
+		let kind = KIND_LIST.iter().position(|kind| *kind == self.kind);
+		(self.date, kind, self.desc.as_str())
+	}
 }
 
-fn extract(line: &str) -> Result<Vec<Event>> {
-	let mut iter = line.split('=');
-	let Some(event_kind) = iter.next() else {
-		return Err("missing 'event kind' slot");
-	};
-	let Some(event) = iter.next() else {
-		return Err("missing 'event' slot");
+impl fmt::Display for Event {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}: {} - {}", self.kind, self.date, self.desc)
+	}
+}
+
+// the events equal as long as they sort the same, see Event::sort_key()
+impl PartialEq for Event {
+	fn eq(&self, other: &Self) -> bool {
+		self.sort_key() == other.sort_key()
+	}
+}
+
+impl Eq for Event {}
+
+impl PartialOrd for Event {
+	fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for Event {
+	fn cmp(&self, other: &Self) -> cmp::Ordering {
+		self.sort_key().cmp(&other.sort_key())
+	}
+}
+
+// parse the value of a "lead,N" slot
+fn parse_lead(value: Option<&str>) -> Result<u32> {
+	let Some(value) = value else {
+		return Err(Error::Parse{ what: "missing lead value" });
 	};
-	if iter.next().is_some() {
-		return Err("extra '=' found");
+	let Ok(lead) = value.parse::<u32>() else {
+		return Err(Error::Parse{ what: "failed to parse lead" });
 	};
-	if event_kind.trim() == "person" {
-		return Ok(person::Person::try_from(event)?.into_events());
+	Ok(lead)
+}
+
+// return <text> padded with spaces up to <width> terminal columns, for aligned output
+pub fn pad_to_width(text: &str, width: usize) -> String {
+	let padding = width.saturating_sub(text.width());
+	format!("{}{}", text, " ".repeat(padding))
+}
+
+// split <value> on the <separator>s found outside of double quotes
+fn split_quoted(value: &str, separator: char) -> Result<Vec<&str>> {
+	let mut slots: Vec<&str> = Vec::new();
+	let mut quoted = false;
+	let mut start = 0;
+	for (i, c) in value.char_indices() {
+		match c {
+			'"' => quoted = !quoted,
+			_ if (c == separator) && !quoted => {
+				slots.push(&value[start..i]);
+				start = i + c.len_utf8();
+			},
+			_ => (),
+		}
+	}
+	if quoted {
+		return Err(Error::Parse{ what: "missing closing '\"'" });
+	}
+	slots.push(&value[start..]);
+	Ok(slots)
+}
+
+// return the trimmed <slot>, without its surrounding double quotes if any
+// the quoted text is taken literally, spaces included
+fn unquote(slot: &str) -> &str {
+	let slot = slot.trim();
+	match slot.strip_prefix('"').and_then(|slot| slot.strip_suffix('"')) {
+		Some(quoted) => quoted,
+		None => slot,
+	}
+}
+
+// return <text> quoted if needed to be read back by unquote()
+fn quote(text: &str) -> String {
+	match text.contains([';', '=']) || (text != text.trim()) {
+		true => format!("\"{}\"", text),
+		false => String::from(text),
+	}
+}
+
+// split an optional trailing slot "key,value" into its parts
+fn parse_option(slot: &str) -> (&str, Option<&str>) {
+	match slot.split_once(',') {
+		Some((key, value)) => (key.trim(), Some(value.trim())),
+		None => (slot.trim(), None),
+	}
+}
+
+// how the entries are turned into events
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Context {
+	// spanning holidays give one event per day if true, a single summary event otherwise
+	pub expand_spans: bool,
+	// what the yearly events do with 29/02 in a year that is not leap
+	pub leap_day: date::LeapDayPolicy,
+	// the events are the next ones from this date on
+	pub today: date::Fixed,
+}
+
+impl Default for Context {
+	fn default() -> Self {
+		Context{ expand_spans: true, leap_day: date::LeapDayPolicy::default(), today: date::Fixed::now() }
 	}
-	if event_kind.trim() == "holiday" {
-		return Ok(holiday::Holiday::try_from(event)?.into_events());
+}
+
+// separator between the slots of an entry, unless the file header sets another one
+pub const DEFAULT_SEPARATOR: char = ';';
+
+pub trait IntoEvents: Sized {
+	fn into_events_in(self, context: &Context) -> Vec<Event>;
+
+	// same as into_events_in, with the default context
+	fn into_events(self) -> Vec<Event> {
+		self.into_events_in(&Context::default())
 	}
-	if event_kind.trim() == "special" {
-		return Ok(special::Special::try_from(event)?.into_events());
+}
+
+// one line of a .rce file
+#[derive(Debug, PartialEq, Eq)]
+pub enum Entry {
+	Person(person::Person),
+	Holiday(holiday::Holiday),
+	Special(special::Special),
+	Countdown(countdown::Countdown),
+	Weekly(weekly::Weekly),
+}
+
+impl TryFrom<&str> for Entry {
+	type Error = Error;
+	fn try_from(line: &str) -> Result<Self> {
+		Entry::parse(line, DEFAULT_SEPARATOR)
 	}
-	Err("no EventKind matched")
+}
+
+impl Entry {
+
+	// same as try_from, the slots being separated by <separator>
+	pub fn parse(line: &str, separator: char) -> Result<Self> {
+		Ok(Entry::parse_lenient(line, separator)?.0)
+	}
+
+	// same as parse, along with the problems that did not prevent parsing, see Person::parse_lenient()
+	pub fn parse_lenient(line: &str, separator: char) -> Result<(Self, Vec<Error>)> {
+		let mut iter = split_quoted(line, '=')?.into_iter();
+		let Some(event_kind) = iter.next() else {
+			return Err(Error::Parse{ what: "missing 'event kind' slot" });
+		};
+		let Some(event) = iter.next() else {
+			return Err(Error::Parse{ what: "missing 'event' slot" });
+		};
+		if iter.next().is_some() {
+			return Err(Error::Parse{ what: "extra '=' found" });
+		};
+		// a person entry gives birthdays, saint days & wedding anniversaries
+		if event_kind.trim().eq_ignore_ascii_case("person") {
+			let (person, warnings) = person::Person::parse_lenient(event, separator)?;
+			return Ok((Entry::Person(person), warnings));
+		}
+		let entry = match EventKind::try_from(event_kind)? {
+			EventKind::Holiday => Entry::Holiday(holiday::Holiday::parse(event, separator)?),
+			EventKind::Special => Entry::Special(special::Special::parse(event, separator)?),
+			EventKind::Countdown => Entry::Countdown(countdown::Countdown::parse(event, separator)?),
+			EventKind::Weekly => Entry::Weekly(weekly::Weekly::parse(event, separator)?),
+			EventKind::Birthday | EventKind::SaintDay | EventKind::Wedding => return Err(Error::Parse{ what: "use a person entry for this kind" }),
+		};
+		Ok((entry, Vec::new()))
+	}
+}
+
+// canonical .rce line
+impl fmt::Display for Entry {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Entry::Person(person) => write!(f, "{}", person),
+			Entry::Holiday(holiday) => write!(f, "{}", holiday),
+			Entry::Special(special) => write!(f, "{}", special),
+			Entry::Countdown(countdown) => write!(f, "{}", countdown),
+			Entry::Weekly(weekly) => write!(f, "{}", weekly),
+		}
+	}
+}
+
+impl IntoEvents for Entry {
+	fn into_events_in(self, context: &Context) -> Vec<Event> {
+		match self {
+			Entry::Person(person) => person.into_events_in(context),
+			Entry::Holiday(holiday) => holiday.into_events_in(context),
+			Entry::Special(special) => special.into_events_in(context),
+			Entry::Countdown(countdown) => countdown.into_events_in(context),
+			Entry::Weekly(weekly) => weekly.into_events_in(context),
+		}
+	}
+}
+
+fn extract(line: &str) -> Result<Vec<Event>> {
+	Ok(Entry::try_from(line)?.into_events())
+}
+
+// parse <line> without keeping its events, return how many it would give
+pub fn validate_line(line: &str) -> Result<usize> {
+	Ok(extract(line)?.len())
 }
 
 // parse line and add events to vector
@@ -78,13 +359,197 @@ pub fn add_from(line: &str, vec: &mut Vec<Event>) -> Result<()> {
 	Ok(())
 }
 
+// parse <lines> lazily, one event at a time, a line failing to parse gives an Err item
+pub fn events_from_lines<I: Iterator<Item = String>>(lines: I) -> impl Iterator<Item = Result<Event>> {
+	lines.flat_map(|line| match extract(&line) {
+		Ok(events) => events.into_iter().map(Ok).collect(),
+		Err(e) => vec![Err(e)],
+	})
+}
+
+// parse every line of the file at <path>
+pub fn load_file<P: AsRef<Path>>(path: P) -> Result<Vec<Event>> {
+	load_file_with(path, false)
+}
+
+// same as load_file, see SanitizedLinesIterator::comment_at_line_start_only
+pub fn load_file_with<P: AsRef<Path>>(path: P, comment_at_line_start_only: bool) -> Result<Vec<Event>> {
+	load_file_located(path, comment_at_line_start_only, &Context::default()).map_err(|e| e.error)
+}
+
+// an error located in a .rce file
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+	pub path: PathBuf,
+	// None if the error is not tied to a line, e.g. the file could not be read
+	pub line: Option<usize>,
+	pub error: Error,
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.line {
+			Some(line) => write!(f, "{}:{}: {}", self.path.display(), line, self.error),
+			None => write!(f, "{}: {}", self.path.display(), self.error),
+		}
+	}
+}
+
+// same as load_file_with, but the error tells the file and line at fault
+pub fn load_file_located<P: AsRef<Path>>(path: P, comment_at_line_start_only: bool, context: &Context) -> std::result::Result<Vec<Event>, ParseError> {
+	let path = path.as_ref();
+	let mut lines = match file::read_lines(path) {
+		Ok(lines) => lines.comment_at_line_start_only(comment_at_line_start_only),
+		Err(error) => return Err(ParseError{ path: path.to_path_buf(), line: None, error }),
+	};
+	load_lines_located(&mut lines, path, context)
+}
+
+// same as load_file_located, but the lines failing to parse are skipped
+// return the events of the other lines along with the errors met
+pub fn load_file_lenient<P: AsRef<Path>>(path: P, comment_at_line_start_only: bool, context: &Context) -> (Vec<Event>, Vec<ParseError>) {
+	let path = path.as_ref();
+	let mut lines = match file::read_lines(path) {
+		Ok(lines) => lines.comment_at_line_start_only(comment_at_line_start_only),
+		Err(error) => return (Vec::new(), vec![ParseError{ path: path.to_path_buf(), line: None, error }]),
+	};
+	load_lines_lenient(&mut lines, path, context)
+}
+
+// same as load_file_located, reading from <reader>, <name> standing for the path in the errors
+pub fn load_reader_located<R: BufRead>(reader: R, name: &Path, comment_at_line_start_only: bool, context: &Context) -> std::result::Result<Vec<Event>, ParseError> {
+	load_lines_located(&mut file::sanitized_lines(reader).comment_at_line_start_only(comment_at_line_start_only), name, context)
+}
+
+// same as load_file_lenient, reading from <reader>, <name> standing for the path in the errors
+pub fn load_reader_lenient<R: BufRead>(reader: R, name: &Path, comment_at_line_start_only: bool, context: &Context) -> (Vec<Event>, Vec<ParseError>) {
+	load_lines_lenient(&mut file::sanitized_lines(reader).comment_at_line_start_only(comment_at_line_start_only), name, context)
+}
+
+// turn the entries of a file into events
+// the overlapping or adjacent spans of a same holiday are merged first, see Holiday::merge_spans
+fn entries_into_events(entries: Vec<Entry>, context: &Context) -> Vec<Event> {
+	let mut events: Vec<Event> = Vec::new();
+	let mut holidays: Vec<holiday::Holiday> = Vec::new();
+	for entry in entries {
+		match entry {
+			Entry::Holiday(holiday) => holidays.push(holiday),
+			entry => events.append(&mut entry.into_events_in(context)),
+		}
+	}
+	for holiday in holiday::Holiday::merge_spans(holidays) {
+		events.append(&mut holiday.into_events_in(context));
+	}
+	events
+}
+
+// parse <lines> up to the first error
+pub fn load_lines_located<R: BufRead>(lines: &mut file::SanitizedLinesIterator<R>, path: &Path, context: &Context) -> std::result::Result<Vec<Event>, ParseError> {
+	let mut entries: Vec<Entry> = Vec::new();
+	while let Some(line) = lines.next() {
+		match Entry::parse(&line, lines.separator().unwrap_or(DEFAULT_SEPARATOR)) {
+			Ok(entry) => entries.push(entry),
+			Err(error) => return Err(ParseError{ path: path.to_path_buf(), line: Some(lines.line_number()), error }),
+		}
+	}
+	Ok(entries_into_events(entries, context))
+}
+
+// parse <lines>, skipping the ones failing to parse
+// the problems of the lines parsed anyway are returned along the errors
+pub fn load_lines_lenient<R: BufRead>(lines: &mut file::SanitizedLinesIterator<R>, path: &Path, context: &Context) -> (Vec<Event>, Vec<ParseError>) {
+	let mut entries: Vec<Entry> = Vec::new();
+	let mut errors: Vec<ParseError> = Vec::new();
+	while let Some(line) = lines.next() {
+		let located = |error| ParseError{ path: path.to_path_buf(), line: Some(lines.line_number()), error };
+		match Entry::parse_lenient(&line, lines.separator().unwrap_or(DEFAULT_SEPARATOR)) {
+			Ok((entry, warnings)) => {
+				errors.extend(warnings.into_iter().map(located));
+				entries.push(entry);
+			},
+			Err(error) => errors.push(located(error)),
+		}
+	}
+	(entries_into_events(entries, context), errors)
+}
+
+// same as load_file_located, but a panic while parsing is turned into an error
+pub fn load_file_robust<P: AsRef<Path>>(path: P, comment_at_line_start_only: bool, context: &Context) -> std::result::Result<Vec<Event>, ParseError> {
+	let path = path.as_ref();
+	catch_panic(path, || load_file_located(path, comment_at_line_start_only, context))
+}
+
+// same as load_lines_located, but a panic while parsing is turned into an error
+pub fn load_lines_robust<R: BufRead>(lines: &mut file::SanitizedLinesIterator<R>, path: &Path, context: &Context) -> std::result::Result<Vec<Event>, ParseError> {
+	// <lines> are not read any further once a panic is caught
+	catch_panic(path, || load_lines_located(lines, path, context))
+}
+
+// run <load>, a panic being turned into an error on the file at <path>
+fn catch_panic<F: FnOnce() -> std::result::Result<Vec<Event>, ParseError>>(path: &Path, load: F) -> std::result::Result<Vec<Event>, ParseError> {
+	match panic::catch_unwind(panic::AssertUnwindSafe(load)) {
+		Ok(result) => result,
+		Err(_) => Err(ParseError{ path: path.to_path_buf(), line: None, error: Error::Parse{ what: "panicked while parsing file" } }),
+	}
+}
+
+// rewrite the file at <path> with one canonical line per entry, sorted
+// the original file is copied to <path>.bak first, comments are not kept
+pub fn normalize_file<P: AsRef<Path>>(path: P, comment_at_line_start_only: bool) -> Result<()> {
+	let path = path.as_ref();
+	let mut lines: Vec<String> = Vec::new();
+	let mut file_lines = file::read_lines(path)?.comment_at_line_start_only(comment_at_line_start_only);
+	while let Some(line) = file_lines.next() {
+		// the canonical lines use the default separator, the header is dropped
+		let entry = Entry::parse(&line, file_lines.separator().unwrap_or(DEFAULT_SEPARATOR))?;
+		// escape the literal '#' so they are not read back as comments
+		lines.push(entry.to_string().replace('#', "\\#"));
+	}
+	lines.sort();
+	let mut backup = path.as_os_str().to_owned();
+	backup.push(".bak");
+	if fs::copy(path, backup).is_err() {
+		return Err(Error::Io{ what: "could not back up file" });
+	}
+	let mut content = lines.join("\n");
+	content.push('\n');
+	if fs::write(path, content).is_err() {
+		return Err(Error::Io{ what: "could not write file" });
+	}
+	Ok(())
+}
+
+// parse every .rce file found in the folder at <path>
+pub fn load_events<P: AsRef<Path>>(path: P) -> Result<Vec<Event>> {
+	let mut events: Vec<Event> = Vec::new();
+	for path in file::find_rce(path)? {
+		events.append(&mut load_file(path)?);
+	}
+	Ok(events)
+}
+
+// return the events of <b> missing from <a> (added) and of <a> missing from <b> (removed)
+pub fn diff<'a>(a: &'a [Event], b: &'a [Event]) -> (Vec<&'a Event>, Vec<&'a Event>) {
+	let uids_a: HashSet<String> = a.iter().map(Event::uid).collect();
+	let uids_b: HashSet<String> = b.iter().map(Event::uid).collect();
+	let added = b.iter().filter(|e| !uids_a.contains(&e.uid())).collect();
+	let removed = a.iter().filter(|e| !uids_b.contains(&e.uid())).collect();
+	(added, removed)
+}
+
 // return vector of references to next events of kind
-pub fn get_next(events: &Vec<Event>, kind: EventKind) -> Vec<&Event> {
-	let now = date::Fixed::now();
+pub fn get_next(events: &[Event], kind: EventKind) -> Vec<&Event> {
+	get_next_on(events, kind, date::Fixed::now())
+}
+
+// return the events of <kind> happening first on or after <today>, several if on the same date
+// countdowns are returned even if before <today>
+pub fn get_next_on(events: &[Event], kind: EventKind, today: date::Fixed) -> Vec<&Event> {
 	let mut next: Vec<&Event> = Vec::new();
-	let filtered = events.iter().filter(|e| (e.kind == kind) && (e.date >= now));
+	// overdue countdowns are kept, they still matter
+	let filtered = events.iter().filter(|e| (e.kind == kind) && ((e.date >= today) || (kind == EventKind::Countdown)));
 	for event in filtered {
-		match next.get(0) {
+		match next.first() {
 			None => next.push(event),
 			Some(e) => match event.date.cmp(&e.date) {
 				cmp::Ordering::Less => { next.clear(); next.push(event); },
@@ -95,3 +560,1618 @@ pub fn get_next(events: &Vec<Event>, kind: EventKind) -> Vec<&Event> {
 	}
 	next
 }
+
+// return the one-off events already past, they will never show up again
+// overdue countdowns are left out, showing how late they are is their point
+pub fn expired(events: &[Event]) -> Vec<&Event> {
+	expired_on(events, date::Fixed::now())
+}
+
+// same as expired, the events before <now> being the past ones
+pub fn expired_on(events: &[Event], now: date::Fixed) -> Vec<&Event> {
+	events.iter()
+		.filter(|e| e.recurring.is_none() && (e.date < now) && (e.kind != EventKind::Countdown))
+		.collect()
+}
+
+// return vector of references to last past events of kind
+// the past events of a recurring kind only come from one-off entries
+pub fn get_overdue(events: &[Event], kind: EventKind) -> Vec<&Event> {
+	get_overdue_on(events, kind, date::Fixed::now())
+}
+
+// same as get_overdue, the events before <now> being the past ones
+pub fn get_overdue_on(events: &[Event], kind: EventKind, now: date::Fixed) -> Vec<&Event> {
+	let mut last: Vec<&Event> = Vec::new();
+	let filtered = events.iter().filter(|e| (e.kind == kind) && (e.date < now));
+	for event in filtered {
+		match last.first() {
+			None => last.push(event),
+			Some(e) => match event.date.cmp(&e.date) {
+				cmp::Ordering::Greater => { last.clear(); last.push(event); },
+				cmp::Ordering::Equal => last.push(event),
+				cmp::Ordering::Less => (),
+			},
+		}
+	}
+	last
+}
+
+// return the last date before <now> <e> happened on
+// the recurring events count on their previous occurrence, the one-off ones if past
+fn previous_occurrence(e: &Event, now: date::Fixed) -> Option<date::Fixed> {
+	match e.recurring {
+		Some(recurring) => date::Fixed::from(recurring).checked_prev_match_on(now, date::LeapDayPolicy::default()),
+		None if e.date < now => Some(e.date),
+		None => None,
+	}
+}
+
+// return the last date before now an event of <kind> happened on, with these events
+pub fn get_previous(events: &[Event], kind: EventKind) -> Option<(date::Fixed, Vec<&Event>)> {
+	get_previous_on(events, kind, date::Fixed::now())
+}
+
+// same as get_previous, the last date before <now> instead
+pub fn get_previous_on(events: &[Event], kind: EventKind, now: date::Fixed) -> Option<(date::Fixed, Vec<&Event>)> {
+	let previous = |e: &Event| previous_occurrence(e, now);
+	let last = events.iter().filter(|e| e.kind == kind).filter_map(previous).max()?;
+	let found = events.iter().filter(|e| (e.kind == kind) && (previous(e) == Some(last))).collect();
+	Some((last, found))
+}
+
+// return the events of <kind> that happened within the last <days_back> days, today excluded
+// each with the date it happened on, most recent first
+pub fn get_recent(events: &[Event], kind: EventKind, days_back: u32) -> Vec<(date::Fixed, &Event)> {
+	get_recent_on(events, kind, days_back, date::Fixed::now())
+}
+
+// same as get_recent, counting the days back from <now>
+pub fn get_recent_on(events: &[Event], kind: EventKind, days_back: u32, now: date::Fixed) -> Vec<(date::Fixed, &Event)> {
+	let mut recent: Vec<(date::Fixed, &Event)> = events.iter()
+		.filter(|e| e.kind == kind)
+		.filter_map(|e| Some((previous_occurrence(e, now)?, e)))
+		.filter(|(date, _)| date.to(now) <= days_back)
+		.collect();
+	recent.sort_by(|(a, _), (b, _)| b.cmp(a));
+	recent
+}
+
+// move every event to the next occurrence of its day & month
+// one-off events are treated as yearly ones, thus are never in the past
+pub fn force_recurring(events: Vec<Event>) -> Vec<Event> {
+	force_recurring_on(events, &Context::default())
+}
+
+// same as force_recurring, the next occurrences being from <context>.today on
+pub fn force_recurring_on(events: Vec<Event>, context: &Context) -> Vec<Event> {
+	events.into_iter()
+		.map(|e| Event {
+			date: e.date.next_match_on(context.today, context.leap_day),
+			recurring: Some(e.recurring.unwrap_or(e.date.recurring())),
+			rule: None,
+			..e
+		})
+		.collect()
+}
+
+// return the soonest holiday happening on or after <now>
+pub fn next_holiday(events: &[Event], now: date::Fixed) -> Option<&Event> {
+	events.iter()
+		.filter(|e| (e.kind == EventKind::Holiday) && (e.date >= now))
+		.min_by_key(|e| e.date)
+}
+
+// return the next <k> yearly occurrences on or after <from> of the recurring events, see date::Rule for the moving ones
+// one-off events are returned as is, if they happen on or after <from>
+pub fn future_occurrences(events: &[Event], from: date::Fixed, k: usize) -> Vec<Event> {
+	let mut occurrences: Vec<Event> = Vec::new();
+	for event in events {
+		if let Some(rule) = event.rule {
+			let dates = (from.year()..=i32::MAX).filter_map(|year| rule.in_year(year)).filter(|date| *date >= from);
+			occurrences.extend(dates.take(k).map(|date| Event { date, ..event.clone() }));
+			continue;
+		}
+		let Some(recurring) = event.recurring else {
+			if event.date >= from {
+				occurrences.push(event.clone());
+			}
+			continue;
+		};
+		let mut year = from.year();
+		if recurring.in_year(year) < from {
+			year += 1;
+		}
+		for year in (year..).take(k) {
+			occurrences.push(Event { date: recurring.in_year(year), ..event.clone() });
+		}
+	}
+	occurrences
+}
+
+// return the events happening in <year>, recurring events moved to <year>, see date::Rule for the moving ones
+// along with the recurring events the leap day <policy> dropped
+pub fn project(events: &[Event], year: i32, policy: date::LeapDayPolicy) -> (Vec<Event>, Vec<&Event>) {
+	let mut projected: Vec<Event> = Vec::new();
+	let mut skipped: Vec<&Event> = Vec::new();
+	for event in events {
+		if let Some(rule) = event.rule {
+			projected.extend(rule.in_year(year).map(|date| Event { date, ..event.clone() }));
+			continue;
+		}
+		match event.recurring {
+			None if event.date.year() == year => projected.push(event.clone()),
+			None => (),
+			Some(recurring) => match recurring.in_year_with(year, policy) {
+				Some(date) => projected.push(Event { date, ..event.clone() }),
+				None => skipped.push(event),
+			},
+		}
+	}
+	(projected, skipped)
+}
+
+// return a warning listing the events dropped by project(), if any
+pub fn skipped_warning(year: i32, skipped: &[&Event]) -> Option<String> {
+	if skipped.is_empty() {
+		return None;
+	}
+	let descs: Vec<&str> = skipped.iter().map(|e| e.desc.as_str()).collect();
+	Some(format!("warning: {} is not leap, skipped the 29/02 events: {}", year, descs.join(", ")))
+}
+
+// date, number of days away & descriptions of the soonest events of a kind
+pub type Soonest = (date::Fixed, u32, String);
+
+// return, for each kind of KIND_LIST, the soonest events on or after <from>
+// the descriptions of the events happening that day are joined by ", "
+pub fn dashboard(events: &[Event], from: date::Fixed) -> Vec<(EventKind, Option<Soonest>)> {
+	KIND_LIST.iter()
+		.map(|kind| {
+			let next = get_next_on(events, *kind, from);
+			let soonest = next.first().map(|first| {
+				let descs: Vec<&str> = next.iter().map(|e| e.desc.as_str()).collect();
+				(first.date, from.to(first.date), descs.join(", "))
+			});
+			(*kind, soonest)
+		})
+		.collect()
+}
+
+// join the first <limit> items with ", ", followed by "(+M more)" if some are left out
+// a <limit> of 0 keeps every item
+pub fn join_limited(items: &[String], limit: usize) -> String {
+	if (limit == 0) || (items.len() <= limit) {
+		return items.join(", ");
+	}
+	format!("{} (+{} more)", items[..limit].join(", "), items.len() - limit)
+}
+
+// return every event happening from now on, sorted by date then by kind as in KIND_LIST
+pub fn all_upcoming(events: &[Event]) -> Vec<&Event> {
+	all_upcoming_on(events, date::Fixed::now())
+}
+
+// same as all_upcoming, from <now> on
+pub fn all_upcoming_on(events: &[Event], now: date::Fixed) -> Vec<&Event> {
+	let rank = |kind: EventKind| KIND_LIST.iter().position(|k| *k == kind);
+	let mut upcoming: Vec<&Event> = events.iter().filter(|e| e.date >= now).collect();
+	upcoming.sort_by_key(|e| (e.date, rank(e.kind)));
+	upcoming
+}
+
+// return the single soonest event from now on, ties broken by kind as in KIND_LIST
+pub fn most_imminent(events: &[Event]) -> Option<&Event> {
+	most_imminent_on(events, date::Fixed::now())
+}
+
+// same as most_imminent, from <now> on
+pub fn most_imminent_on(events: &[Event], now: date::Fixed) -> Option<&Event> {
+	let rank = |kind: EventKind| KIND_LIST.iter().position(|k| *k == kind);
+	events.iter()
+		.filter(|e| e.date >= now)
+		.min_by_key(|e| (e.date, rank(e.kind)))
+}
+
+// return the events announced by <now>, i.e. the ones with a lead time
+// happening in [now, now+lead]
+pub fn get_upcoming(events: &[Event], now: date::Fixed) -> Vec<&Event> {
+	let mut upcoming: Vec<&Event> = events.iter()
+		.filter(|e| e.lead.is_some_and(|lead| (e.date >= now) && (now.to(e.date) <= lead)))
+		.collect();
+	upcoming.sort_by_key(|e| e.date);
+	upcoming
+}
+
+// return true if any event happens within <days> days from now
+pub fn any_within(events: &[Event], days: u32) -> bool {
+	any_within_on(events, days, date::Fixed::now())
+}
+
+// same as any_within, counting the days from <now>
+pub fn any_within_on(events: &[Event], days: u32, now: date::Fixed) -> bool {
+	!within_days_on(events, days, now).is_empty()
+}
+
+// return the events happening within <days> days from now, today included, sorted
+pub fn within_days(events: &[Event], days: u32) -> Vec<&Event> {
+	within_days_on(events, days, date::Fixed::now())
+}
+
+// same as within_days, counting the days from <now>
+pub fn within_days_on(events: &[Event], days: u32, now: date::Fixed) -> Vec<&Event> {
+	let mut found: Vec<&Event> = events.iter()
+		.filter(|e| (e.date >= now) && (now.to(e.date) <= days))
+		.collect();
+	found.sort();
+	found
+}
+
+// merge the events sharing the same kind & date into one
+// distinct descriptions are joined with " / "
+pub fn merge(events: Vec<Event>) -> Vec<Event> {
+	let mut merged: Vec<Event> = Vec::new();
+	for event in events {
+		let same = merged.iter_mut().find(|e| (e.kind == event.kind) && (e.date == event.date));
+		match same {
+			None => merged.push(event),
+			Some(e) => {
+				if !e.desc.split(" / ").any(|desc| desc == event.desc) {
+					e.desc += " / ";
+					e.desc += &event.desc;
+				}
+			},
+		}
+	}
+	merged
+}
+
+// return the events equal to an earlier one, paired with the first of them
+// i.e. the events of same kind, date & description, see Event::uid()
+pub fn find_duplicates(events: &[Event]) -> Vec<(&Event, &Event)> {
+	let mut seen: HashMap<String, &Event> = HashMap::new();
+	let mut duplicates: Vec<(&Event, &Event)> = Vec::new();
+	for event in events {
+		match seen.get(&event.uid()) {
+			Some(first) => duplicates.push((first, event)),
+			None => { seen.insert(event.uid(), event); },
+		}
+	}
+	duplicates
+}
+
+// keep the events happening in [since, until], either bound may be omitted
+pub fn between(events: Vec<Event>, since: Option<date::Fixed>, until: Option<date::Fixed>) -> Vec<Event> {
+	events.into_iter()
+		.filter(|e| since.is_none_or(|since| since <= e.date))
+		.filter(|e| until.is_none_or(|until| e.date <= until))
+		.collect()
+}
+
+// keep the events carrying <tag>
+pub fn with_tag(events: Vec<Event>, tag: &str) -> Vec<Event> {
+	events.into_iter()
+		.filter(|e| e.tags.iter().any(|t| t == tag))
+		.collect()
+}
+
+// return the events happening in [from, to], sorted, empty if <from> is after <to>
+pub fn in_range(events: &[Event], from: date::Fixed, to: date::Fixed) -> Vec<&Event> {
+	let mut found: Vec<&Event> = events.iter()
+		.filter(|e| (from <= e.date) && (e.date <= to))
+		.collect();
+	found.sort();
+	found
+}
+
+// return the events by date, in date order, keeping their order within a date
+pub fn group_by_date(events: &[Event]) -> BTreeMap<date::Fixed, Vec<&Event>> {
+	let mut groups: BTreeMap<date::Fixed, Vec<&Event>> = BTreeMap::new();
+	for event in events {
+		groups.entry(event.date).or_default().push(event);
+	}
+	groups
+}
+
+// return the number of events happening in each month of <year>
+// recurring events are counted once, in the month they fall in that year if moving
+// one-off events only if they fall in <year>
+pub fn histogram(events: &[Event], year: i32) -> [usize; 12] {
+	let mut counts = [0; 12];
+	for event in events {
+		let date = match (event.rule, event.recurring) {
+			(Some(rule), _) => rule.in_year(year).map(date::Fixed::recurring),
+			(None, Some(recurring)) => Some(recurring),
+			(None, None) if event.date.year() == year => Some(event.date.recurring()),
+			(None, None) => None,
+		};
+		let Some(date) = date else {
+			continue;
+		};
+		counts[(date.month()-1) as usize] += 1;
+	}
+	counts
+}
+
+// render the histogram as one bar of '#' per month
+pub fn render_histogram(counts: &[usize; 12]) -> String {
+	const MONTHS: [&str; 12] = [
+		"Jan", "Feb", "Mar", "Apr", "May", "Jun",
+		"Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+	];
+	let mut text = String::new();
+	for (month, count) in MONTHS.iter().zip(counts) {
+		let bar = "#".repeat(*count);
+		text += format!("{} | {} {}\n", month, bar.green(), count).as_str();
+	}
+	text
+}
+
+// return the GraphViz color matching the terminal color of <kind>
+fn dot_color(kind: EventKind) -> &'static str {
+	match kind {
+		EventKind::Birthday => "red",
+		EventKind::SaintDay => "blue",
+		EventKind::Wedding => "green",
+		EventKind::Holiday => "yellow",
+		EventKind::Special => "cyan",
+		EventKind::Countdown => "magenta",
+		EventKind::Weekly => "gray",
+	}
+}
+
+// escape <text> for a double-quoted DOT string
+fn dot_escape(text: &str) -> String {
+	let mut escaped = String::new();
+	for c in text.chars() {
+		match c {
+			'"' | '\\' => {
+				escaped.push('\\');
+				escaped.push(c);
+			},
+			'\n' => escaped.push_str("\\n"),
+			_ => escaped.push(c),
+		}
+	}
+	escaped
+}
+
+// render the events as a GraphViz DOT timeline, one node per event, in date order
+pub fn to_dot(events: &[Event]) -> String {
+	let mut sorted: Vec<&Event> = events.iter().collect();
+	sorted.sort_by_key(|e| e.date);
+	let mut text = String::from("digraph timeline {\n\trankdir=LR;\n\tnode [shape=box, style=filled];\n");
+	for (i, e) in sorted.iter().enumerate() {
+		let label = format!("{}\\n{}", e.date, dot_escape(&e.desc));
+		text += format!("\te{} [label=\"{}\", fillcolor={}];\n", i, label, dot_color(e.kind)).as_str();
+	}
+	for i in 1..sorted.len() {
+		text += format!("\te{} -> e{};\n", i-1, i).as_str();
+	}
+	text += "}\n";
+	text
+}
+
+// escape <text> for an iCalendar TEXT value
+fn ics_escape(text: &str) -> String {
+	let mut escaped = String::new();
+	for c in text.chars() {
+		match c {
+			'\\' | ';' | ',' => {
+				escaped.push('\\');
+				escaped.push(c);
+			},
+			'\n' => escaped.push_str("\\n"),
+			_ => escaped.push(c),
+		}
+	}
+	escaped
+}
+
+// append <line> to <text>, folded every 75 octets as required by RFC 5545
+fn ics_push_line(text: &mut String, line: &str) {
+	let mut octets = 0;
+	for c in line.chars() {
+		if octets + c.len_utf8() > 75 {
+			text.push_str("\r\n ");
+			octets = 1;
+		}
+		text.push(c);
+		octets += c.len_utf8();
+	}
+	text.push_str("\r\n");
+}
+
+// return the date as an iCalendar DATE value, YYYYMMDD
+fn ics_date(date: date::Fixed) -> String {
+	let recurring = date.recurring();
+	format!("{:04}{:02}{:02}", date.year(), recurring.month(), recurring.day())
+}
+
+// render the events as an iCalendar file, one all-day VEVENT per event
+// recurring events come back every year
+pub fn export_ics(events: &[Event]) -> String {
+	let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+	let mut text = String::new();
+	ics_push_line(&mut text, "BEGIN:VCALENDAR");
+	ics_push_line(&mut text, "VERSION:2.0");
+	ics_push_line(&mut text, "PRODID:-//rustminder//rustminder//EN");
+	for e in events {
+		ics_push_line(&mut text, "BEGIN:VEVENT");
+		ics_push_line(&mut text, format!("UID:{}@rustminder", ics_escape(&e.uid())).as_str());
+		ics_push_line(&mut text, format!("DTSTAMP:{}", stamp).as_str());
+		ics_push_line(&mut text, format!("DTSTART;VALUE=DATE:{}", ics_date(e.date)).as_str());
+		ics_push_line(&mut text, format!("DTEND;VALUE=DATE:{}", ics_date(e.date.next())).as_str());
+		if e.recurring.is_some() {
+			ics_push_line(&mut text, "RRULE:FREQ=YEARLY");
+		}
+		ics_push_line(&mut text, format!("SUMMARY:{}", ics_escape(&e.desc)).as_str());
+		ics_push_line(&mut text, format!("CATEGORIES:{}", ics_escape(e.kind.name())).as_str());
+		ics_push_line(&mut text, "END:VEVENT");
+	}
+	ics_push_line(&mut text, "END:VCALENDAR");
+	text
+}
+
+// escape <text> for a JSON string
+fn json_escape(text: &str) -> String {
+	let mut escaped = String::new();
+	for c in text.chars() {
+		match c {
+			'"' | '\\' => {
+				escaped.push('\\');
+				escaped.push(c);
+			},
+			'\n' => escaped.push_str("\\n"),
+			'\t' => escaped.push_str("\\t"),
+			c if c.is_control() => escaped.push_str(format!("\\u{:04x}", c as u32).as_str()),
+			_ => escaped.push(c),
+		}
+	}
+	escaped
+}
+
+// render the events as a JSON array, days until each event counted from <now>
+pub fn to_json(events: &[&Event], now: date::Fixed) -> String {
+	let entries: Vec<String> = events.iter()
+		.map(|e| {
+			let recurring = e.date.recurring();
+			format!(
+				"{{\"kind\":\"{}\",\"date\":\"{:04}-{:02}-{:02}\",\"days_until\":{},\"desc\":\"{}\"}}",
+				e.kind.name(), e.date.year(), recurring.month(), recurring.day(), now.to(e.date), json_escape(&e.desc)
+			)
+		})
+		.collect();
+	format!("[{}]", entries.join(","))
+}
+
+// escape the characters that would break a TSV row, so that each event keeps a single line
+fn tsv_escape(text: &str) -> String {
+	let mut escaped = String::new();
+	for c in text.chars() {
+		match c {
+			'\\' => escaped.push_str("\\\\"),
+			'\t' => escaped.push_str("\\t"),
+			'\n' => escaped.push_str("\\n"),
+			'\r' => escaped.push_str("\\r"),
+			_ => escaped.push(c),
+		}
+	}
+	escaped
+}
+
+// render the events as tab-separated values, after a "kind, date, desc" header row
+pub fn export_tsv(events: &[Event]) -> String {
+	let mut text = String::from("kind\tdate\tdesc\n");
+	for e in events {
+		let recurring = e.date.recurring();
+		text.push_str(format!(
+			"{}\t{:04}-{:02}-{:02}\t{}\n",
+			e.kind.name(), e.date.year(), recurring.month(), recurring.day(), tsv_escape(&e.desc)
+		).as_str());
+	}
+	text
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn new_holiday(date: date::Fixed) -> Event {
+		Event {
+			kind: EventKind::Holiday,
+			date,
+			desc: String::from("holiday"),
+			recurring: None,
+			rule: None,
+			note: None,
+			lead: None,
+			tags: Vec::new(),
+		}
+	}
+
+	fn new_event(kind: EventKind, date: date::Fixed, recurring: bool) -> Event {
+		Event {
+			kind,
+			date,
+			desc: String::from("event"),
+			recurring: if recurring { Some(date.recurring()) } else { None },
+			rule: None,
+			note: None,
+			lead: None,
+			tags: Vec::new(),
+		}
+	}
+
+	#[test]
+	fn kind_display_no_color() {
+		colored::control::set_override(false);
+		let text = format!("{} {}", EventKind::Birthday, EventKind::Countdown);
+		colored::control::unset_override();
+		assert_eq!(
+			text,
+			"birthday countdown"
+		);
+	}
+	#[test]
+	fn event_display() {
+		let mut event = new_event(EventKind::Special, date::Fixed::new(4,7,2030), false);
+		event.desc = String::from("IMPORTANT");
+		assert_eq!(
+			event.to_string(),
+			format!("{}: 04/07/2030 - IMPORTANT", EventKind::Special)
+		);
+	}
+	#[test]
+	fn event_display_width() {
+		let mut event = new_holiday(date::Fixed::new(1,1,2030));
+		event.desc = String::from("José");
+		assert_eq!(
+			(event.desc.len(), event.display_width()),
+			(5, 4)
+		);
+		event.desc = String::from("Zoë 生日");
+		assert_eq!(
+			(event.desc.len(), event.display_width()),
+			(11, 8)
+		);
+		assert_eq!(
+			pad_to_width("生日", 6),
+			"生日  "
+		);
+		assert_eq!(
+			pad_to_width("José", 2),
+			"José"
+		);
+	}
+	#[test]
+	fn event_sort() {
+		let date = date::Fixed::new(25,12,2030);
+		let mut b = new_event(EventKind::Holiday, date, true);
+		b.desc = String::from("b");
+		let mut a = new_event(EventKind::Holiday, date, true);
+		a.desc = String::from("a");
+		let birthday = new_event(EventKind::Birthday, date, true);
+		let sooner = new_event(EventKind::Special, date::Fixed::new(1,1,2030), false);
+		let mut events = [b, a, birthday, sooner];
+		events.sort();
+		assert_eq!(
+			events.iter().map(|e| (e.kind, e.desc.as_str())).collect::<Vec<_>>(),
+			vec![
+				(EventKind::Special, "event"),
+				(EventKind::Birthday, "event"),
+				(EventKind::Holiday, "a"),
+				(EventKind::Holiday, "b"),
+			]
+		);
+	}
+	#[test]
+	fn merge_same_date() {
+		let date = date::Fixed::new(25,12,2030);
+		let mut first = new_event(EventKind::Holiday, date, true);
+		first.desc = String::from("Christmas");
+		let mut second = new_event(EventKind::Holiday, date, true);
+		second.desc = String::from("Noël");
+		let mut third = new_event(EventKind::Holiday, date, true);
+		third.desc = String::from("Christmas");
+		let other = new_event(EventKind::Special, date, false);
+		let merged = merge(vec![first, second, third, other]);
+		assert_eq!(
+			merged.len(),
+			2
+		);
+		assert_eq!(
+			merged[0].desc,
+			"Christmas / Noël"
+		);
+		assert_eq!(
+			merged[1].desc,
+			"event"
+		);
+	}
+	fn new_dataset() -> Vec<Event> {
+		vec![
+			new_event(EventKind::Special, date::Fixed::new(1,1,2030), false),
+			new_event(EventKind::Special, date::Fixed::new(1,2,2030), false),
+			new_event(EventKind::Special, date::Fixed::new(1,3,2030), false),
+		]
+	}
+
+	#[test]
+	fn between_since() {
+		let events = between(new_dataset(), Some(date::Fixed::new(1,2,2030)), None);
+		assert_eq!(
+			events.iter().map(|e| e.date).collect::<Vec<_>>(),
+			vec![date::Fixed::new(1,2,2030), date::Fixed::new(1,3,2030)]
+		);
+	}
+	#[test]
+	fn between_until() {
+		let events = between(new_dataset(), None, Some(date::Fixed::new(1,2,2030)));
+		assert_eq!(
+			events.iter().map(|e| e.date).collect::<Vec<_>>(),
+			vec![date::Fixed::new(1,1,2030), date::Fixed::new(1,2,2030)]
+		);
+	}
+	#[test]
+	fn between_both() {
+		let events = between(
+			new_dataset(),
+			Some(date::Fixed::new(2,1,2030)),
+			Some(date::Fixed::new(28,2,2030))
+		);
+		assert_eq!(
+			events.iter().map(|e| e.date).collect::<Vec<_>>(),
+			vec![date::Fixed::new(1,2,2030)]
+		);
+	}
+	#[test]
+	fn tags_through_add_from() {
+		let mut events = vec![new_event(EventKind::Birthday, date::Fixed::new(3,1,2030), true)];
+		events[0].tags.push(String::from("family"));
+		add_from("special = meeting ; 1,1,2030 ; tag, work", &mut events).unwrap();
+		add_from("holiday = Christmas ; 25,12", &mut events).unwrap();
+		assert_eq!(
+			events.iter().map(|e| e.tags.clone()).collect::<Vec<_>>(),
+			vec![vec![String::from("family")], vec![String::from("work")], Vec::new()]
+		);
+	}
+	#[test]
+	fn with_tag_filter() {
+		let mut events = new_dataset();
+		events[0].tags = vec![String::from("work")];
+		events[2].tags = vec![String::from("health"), String::from("work")];
+		events[1].tags = vec![String::from("health")];
+		assert_eq!(
+			with_tag(events.clone(), "work").iter().map(|e| e.date).collect::<Vec<_>>(),
+			vec![date::Fixed::new(1,1,2030), date::Fixed::new(1,3,2030)]
+		);
+		assert!(with_tag(events, "home").is_empty());
+	}
+	#[test]
+	fn in_range_bounds() {
+		let events = new_dataset();
+		assert_eq!(
+			in_range(&events, date::Fixed::new(1,2,2030), date::Fixed::new(1,3,2030)).iter().map(|e| e.date).collect::<Vec<_>>(),
+			vec![date::Fixed::new(1,2,2030), date::Fixed::new(1,3,2030)]
+		);
+		assert_eq!(
+			in_range(&events, date::Fixed::new(1,1,2030), date::Fixed::new(1,1,2030)).len(),
+			1
+		);
+		assert!(in_range(&events, date::Fixed::new(2,1,2030), date::Fixed::new(31,1,2030)).is_empty());
+	}
+	#[test]
+	fn in_range_inverted() {
+		assert!(in_range(&new_dataset(), date::Fixed::new(1,3,2030), date::Fixed::new(1,1,2030)).is_empty());
+	}
+	#[test]
+	fn group_by_date_buckets() {
+		let mut events = new_dataset();
+		events.reverse();
+		events.push(new_event(EventKind::Holiday, date::Fixed::new(1,2,2030), true));
+		let groups = group_by_date(&events);
+		assert_eq!(
+			groups.keys().copied().collect::<Vec<_>>(),
+			vec![date::Fixed::new(1,1,2030), date::Fixed::new(1,2,2030), date::Fixed::new(1,3,2030)]
+		);
+		assert_eq!(
+			groups[&date::Fixed::new(1,2,2030)].iter().map(|e| e.kind).collect::<Vec<_>>(),
+			vec![EventKind::Special, EventKind::Holiday]
+		);
+	}
+	#[test]
+	fn get_next_on_reference() {
+		let events = new_dataset();
+		let next = |today| get_next_on(&events, EventKind::Special, today).iter().map(|e| e.date).collect::<Vec<_>>();
+		assert_eq!(
+			next(date::Fixed::new(1,1,2030)),
+			vec![date::Fixed::new(1,1,2030)]
+		);
+		assert_eq!(
+			next(date::Fixed::new(2,1,2030)),
+			vec![date::Fixed::new(1,2,2030)]
+		);
+		assert!(next(date::Fixed::new(2,3,2030)).is_empty());
+	}
+	#[test]
+	fn find_duplicates_pairs() {
+		let mut events = Vec::new();
+		add_from("special = party ; 1,1,2030", &mut events).unwrap();
+		add_from("special = other ; 1,1,2030", &mut events).unwrap();
+		add_from("special =  party;1,1,2030", &mut events).unwrap();
+		add_from("holiday = party ; 1,1,2030", &mut events).unwrap();
+		let duplicates = find_duplicates(&events);
+		assert_eq!(
+			duplicates.len(),
+			1
+		);
+		assert!(std::ptr::eq(duplicates[0].0, &events[0]));
+		assert!(std::ptr::eq(duplicates[0].1, &events[2]));
+		assert!(find_duplicates(&events[..2]).is_empty());
+	}
+	#[test]
+	fn within_days_boundary() {
+		let today = date::Fixed::new(25,12,2029);
+		let events = new_dataset();
+		let on_boundary = within_days_on(&events, 7, today);
+		let past_boundary = within_days_on(&events, 6, today);
+		let all = within_days_on(&events, 66, today);
+		assert_eq!(
+			on_boundary.iter().map(|e| e.date).collect::<Vec<_>>(),
+			vec![date::Fixed::new(1,1,2030)]
+		);
+		assert!(past_boundary.is_empty());
+		assert_eq!(
+			all.len(),
+			3
+		);
+	}
+	#[test]
+	fn expired_one_off() {
+		let today = date::Fixed::new(1,6,2030);
+		let context = Context{ expand_spans: false, today, ..Context::default() };
+		let mut events = Vec::new();
+		for line in [
+			"special = past ; 1,1,2030",
+			"special = future ; 1,1,2031",
+			"holiday = recurring ; 1,1",
+			"holiday = past span ; 1,1,2030 ; 5,1,2030",
+			"countdown = overdue ; 1,1,2030",
+		] {
+			events.append(&mut Entry::try_from(line).unwrap().into_events_in(&context));
+		}
+		let expired: Vec<&str> = expired_on(&events, today).iter().map(|e| e.desc.as_str()).collect();
+		assert_eq!(
+			expired,
+			vec!["past", "past span (5 days)"]
+		);
+	}
+	#[test]
+	fn get_recent_window() {
+		let context = Context{ today: date::Fixed::new(10,1,2030), ..Context::default() };
+		let mut events = Vec::new();
+		for line in [
+			"special = two days ago ; 8,1,2030",
+			"special = today ; 10,1,2030",
+			"holiday = recurring ; 9,1",
+		] {
+			events.append(&mut Entry::try_from(line).unwrap().into_events_in(&context));
+		}
+		let descs = |days_back: u32| -> Vec<(date::Fixed, String)> {
+			get_recent_on(&events, EventKind::Special, days_back, context.today).into_iter().map(|(date, e)| (date, e.desc.clone())).collect()
+		};
+		let three = descs(3);
+		let one = descs(1);
+		let holiday = get_recent_on(&events, EventKind::Holiday, 1, context.today).into_iter().map(|(date, _)| date).collect::<Vec<_>>();
+		assert_eq!(
+			three,
+			vec![(date::Fixed::new(8,1,2030), String::from("two days ago"))]
+		);
+		assert!(one.is_empty());
+		assert_eq!(
+			holiday,
+			vec![date::Fixed::new(9,1,2030)]
+		);
+	}
+	#[test]
+	fn get_previous_recurring() {
+		let context = Context{ today: date::Fixed::new(10,1,2030), ..Context::default() };
+		let mut events = Vec::new();
+		for line in [
+			"holiday = Christmas ; 25,12",
+			"holiday = New Year ; 1,1",
+			"holiday = Jour de l'an ; 1,1",
+			"holiday = Summer ; 1,7",
+			"special = one-off ; 1,1,2025",
+		] {
+			events.append(&mut Entry::try_from(line).unwrap().into_events_in(&context));
+		}
+		let holiday = get_previous_on(&events, EventKind::Holiday, context.today).map(|(date, found)| (date, found.len()));
+		let special = get_previous_on(&events, EventKind::Special, context.today).map(|(date, found)| (date, found.len()));
+		let none = get_previous_on(&events, EventKind::Birthday, context.today).is_none();
+		assert_eq!(
+			holiday,
+			Some((date::Fixed::new(1,1,2030), 2))
+		);
+		assert_eq!(
+			special,
+			Some((date::Fixed::new(1,1,2025), 1))
+		);
+		assert!(none);
+	}
+	#[test]
+	fn get_previous_at_min_year() {
+		let now = date::Fixed::new(1,6,i32::MIN);
+		let events = Entry::try_from("holiday = Christmas ; 25,12").unwrap().into_events_in(&Context{ today: now, ..Context::default() });
+		assert_eq!(
+			get_previous_on(&events, EventKind::Holiday, now),
+			None
+		);
+	}
+	#[test]
+	fn diff_added_removed() {
+		let a = new_dataset();
+		let mut b = new_dataset();
+		let removed = b.remove(0);
+		b.push(new_event(EventKind::Special, date::Fixed::new(1,4,2030), false));
+		let (added, gone) = diff(&a, &b);
+		assert_eq!(
+			added.iter().map(|e| e.uid()).collect::<Vec<_>>(),
+			vec![b[2].uid()]
+		);
+		assert_eq!(
+			gone.iter().map(|e| e.uid()).collect::<Vec<_>>(),
+			vec![removed.uid()]
+		);
+	}
+	#[test]
+	fn past_special() {
+		let past = date::Fixed::new(1,1,2000);
+		let events = vec![new_event(EventKind::Special, past, false)];
+		assert!(
+			get_next(&events, EventKind::Special)
+			.is_empty()
+		);
+		assert_eq!(
+			get_overdue(&events, EventKind::Special).iter().map(|e| e.date).collect::<Vec<_>>(),
+			vec![past]
+		);
+		let events = force_recurring(events);
+		assert_eq!(
+			get_next(&events, EventKind::Special).iter().map(|e| e.date).collect::<Vec<_>>(),
+			vec![past.next_match()]
+		);
+		assert!(
+			get_overdue(&events, EventKind::Special)
+			.is_empty()
+		);
+	}
+	#[test]
+	fn located_error() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("family.rce");
+		std::fs::write(&path, "# comment\n\nspecial = ok ; 1,1,2030\nspecial = bad ; 1,13,2030\n").unwrap();
+		let error = load_file_located(&path, false, &Context::default()).unwrap_err();
+		assert_eq!(
+			error.line,
+			Some(4)
+		);
+		assert_eq!(
+			error.to_string(),
+			format!("{}:4: month out of range", path.display())
+		);
+		assert_eq!(
+			load_file_with(&path, false).unwrap_err(),
+			Error::OutOfRange{ what: "month out of range" }
+		);
+	}
+	#[test]
+	fn validate_lines() {
+		assert_eq!(
+			validate_line("special = a ; 1,1,2030"),
+			Ok(1)
+		);
+		assert_eq!(
+			validate_line("person = John, SMITH, ; 02,05,1970 ; 24,06 ; 12,06,1995"),
+			Ok(3)
+		);
+		assert_eq!(
+			validate_line("special = broken"),
+			Err(Error::Parse{ what: "missing 'date' slot" })
+		);
+	}
+	#[test]
+	fn events_from_lines_lazy() {
+		let lines = [
+			"special = a ; 1,1,2030",
+			"special = broken",
+			"person = John, SMITH, ; 02,05,1970 ; 24,06 ;",
+		].into_iter().map(String::from);
+		let items: Vec<Result<Event>> = events_from_lines(lines).collect();
+		assert_eq!(
+			items.len(),
+			1 + 1 + 2
+		);
+		assert_eq!(
+			items[0].as_ref().map(|e| e.desc.as_str()),
+			Ok("a")
+		);
+		assert_eq!(
+			items[1].as_ref().map(|e| e.desc.as_str()),
+			Err(&Error::Parse{ what: "missing 'date' slot" })
+		);
+		assert_eq!(
+			items[2..].iter().map(|item| item.as_ref().unwrap().kind).collect::<Vec<_>>(),
+			vec![EventKind::Birthday, EventKind::SaintDay]
+		);
+	}
+	#[test]
+	fn load_from_reader() {
+		let reader = std::io::Cursor::new("special = a ; 1,1,2030\nspecial = broken\nholiday = b ; 1,5\n");
+		let (events, errors) = load_reader_lenient(reader, Path::new("<stdin>"), false, &Context::default());
+		assert_eq!(
+			events.len(),
+			2
+		);
+		assert_eq!(
+			errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
+			vec!["<stdin>:2: missing 'date' slot"]
+		);
+		let reader = std::io::Cursor::new("special = a ; 1,1,2030\nspecial = broken\n");
+		assert_eq!(
+			load_reader_located(reader, Path::new("<stdin>"), false, &Context::default()).unwrap_err().line,
+			Some(2)
+		);
+	}
+	#[test]
+	fn load_warning_kept() {
+		let reader = std::io::Cursor::new("person = Rustacean,, ; 1,1 ; auto ;\n");
+		let (events, errors) = load_reader_lenient(reader, Path::new("<stdin>"), false, &Context::default());
+		assert_eq!(
+			events.iter().map(|e| e.kind).collect::<Vec<_>>(),
+			vec![EventKind::Birthday]
+		);
+		assert_eq!(
+			errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
+			vec!["<stdin>:1: no saint day known for the first name, skipped"]
+		);
+	}
+	#[test]
+	fn load_with_separator_header() {
+		let reader = std::io::Cursor::new(concat!(
+			"#!sep: |\n",
+			"special = cafe; bar | 1,1,2030\n",
+			"holiday = Summer | 1,7,2030 | 31,8,2030\n",
+			"person = John, SMITH, | 02,05,1970 | |\n",
+		));
+		let events = load_reader_located(reader, Path::new("<stdin>"), false, &Context::default()).unwrap();
+		assert_eq!(
+			events[0].desc,
+			"cafe; bar"
+		);
+		assert_eq!(
+			events.len(),
+			1 + 62 + 1
+		);
+		let reader = std::io::Cursor::new("special = a | 1,1,2030\n");
+		assert!(
+			load_reader_located(reader, Path::new("<stdin>"), false, &Context::default())
+			.is_err()
+		);
+		assert_eq!(
+			Entry::parse("special = a | 1,1,2030", '|'),
+			Entry::try_from("special = a ; 1,1,2030")
+		);
+	}
+	#[test]
+	fn load_merge_spans() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("spans.rce");
+		std::fs::write(&path, concat!(
+			"holiday = Summer ; 1,7,2030 ; 31,7,2030\n",
+			"special = a ; 1,1,2030\n",
+			"holiday = Summer ; 15,7,2030 ; 31,8,2030\n",
+			"holiday = Winter ; 1,9,2030 ; 2,9,2030\n",
+		)).unwrap();
+		let events = load_file(&path).unwrap();
+		let summer: Vec<&Event> = events.iter().filter(|e| e.desc.starts_with("Summer")).collect();
+		assert_eq!(
+			summer.len(),
+			62
+		);
+		assert_eq!(
+			summer[0].desc,
+			"Summer (61 days remaining)"
+		);
+		assert_eq!(
+			events.len(),
+			1 + 62 + 2
+		);
+	}
+	#[test]
+	fn lenient_collect_errors() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("mixed.rce");
+		std::fs::write(&path, "special = a ; 1,1,2030\nspecial = broken\nspecial = b ; 2,1,2030\n").unwrap();
+		let (events, errors) = load_file_lenient(&path, false, &Context::default());
+		assert_eq!(
+			events.len(),
+			2
+		);
+		assert_eq!(
+			errors.len(),
+			1
+		);
+		assert_eq!(
+			errors[0].line,
+			Some(2)
+		);
+	}
+	// an entry whose conversion panics, whatever the build profile
+	struct Panicking;
+	impl IntoEvents for Panicking {
+		fn into_events_in(self, _: &Context) -> Vec<Event> {
+			panic!("conversion failed")
+		}
+	}
+	#[test]
+	fn robust_skip_panic() {
+		let dir = tempfile::tempdir().unwrap();
+		let good = dir.path().join("good.rce");
+		std::fs::write(&good, "special = ok ; 1,1,2030\n").unwrap();
+		let bad = dir.path().join("bad.rce");
+		assert_eq!(
+			load_file_robust(&good, false, &Context::default()).map(|events| events.len()),
+			Ok(1)
+		);
+		assert_eq!(
+			catch_panic(&bad, || Ok(Panicking.into_events())),
+			Err(ParseError{ path: bad.clone(), line: None, error: Error::Parse{ what: "panicked while parsing file" } })
+		);
+	}
+	#[test]
+	fn span_at_max_year() {
+		let events = extract("holiday = last days ; 30,12,2147483647 ; 31,12,2147483647").unwrap();
+		assert_eq!(
+			events.len(),
+			2
+		);
+	}
+	#[test]
+	fn next_holiday_soonest() {
+		let mut events = vec![
+			new_event(EventKind::Holiday, date::Fixed::new(25,12,2030), true),
+			new_event(EventKind::Special, date::Fixed::new(2,1,2030), false),
+			new_event(EventKind::Holiday, date::Fixed::new(14,7,2030), true),
+			new_event(EventKind::Holiday, date::Fixed::new(1,1,2030), true),
+			new_event(EventKind::Holiday, date::Fixed::new(1,5,2030), false),
+		];
+		events[2].desc = String::from("soonest");
+		assert_eq!(
+			next_holiday(&events, date::Fixed::new(2,5,2030)).map(|e| e.desc.as_str()),
+			Some("soonest")
+		);
+		assert!(
+			next_holiday(&events, date::Fixed::new(26,12,2030))
+			.is_none()
+		);
+	}
+	#[test]
+	fn future_occurrences_leap() {
+		let mut birthday = new_event(EventKind::Birthday, date::Fixed::new(28,2,2023), true);
+		birthday.recurring = Some(date::Recurring::new(29,2));
+		let events = vec![
+			birthday,
+			new_event(EventKind::Special, date::Fixed::new(1,1,2023), false),
+			new_event(EventKind::Special, date::Fixed::new(1,1,2025), false),
+		];
+		let occurrences = future_occurrences(&events, date::Fixed::new(1,3,2023), 3);
+		assert_eq!(
+			occurrences.iter().map(|e| e.date).collect::<Vec<_>>(),
+			vec![
+				date::Fixed::new(29,2,2024),
+				date::Fixed::new(28,2,2025),
+				date::Fixed::new(28,2,2026),
+				date::Fixed::new(1,1,2025),
+			]
+		);
+	}
+	#[test]
+	fn normalize_round_trip() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("messy.rce");
+		let messy = "\
+# a comment
+special=IMPORTANT;4,7,2030   # inline comment
+   holiday =  Summer;1 ,7,2030 ;  31,8,2030
+person =Santa,CLAUS,  St Nicholas;25,12;6,12;
+holiday= Christmas ;25,12
+person = Jane,,;1,1,1990;;;note,loves tulips, daisies
+special = cafe \\#1 ; 1,1,2030 # comment
+";
+		std::fs::write(&path, messy).unwrap();
+		let before = load_file(&path).unwrap();
+		normalize_file(&path, false).unwrap();
+		assert_eq!(
+			std::fs::read_to_string(path.with_extension("rce.bak")).unwrap(),
+			messy
+		);
+		assert_eq!(
+			std::fs::read_to_string(&path).unwrap(),
+			"\
+holiday = Christmas ; 25,12
+holiday = Summer ; 01,07,2030 ; 31,08,2030
+person = Jane, ,  ; 01,01,1990 ; ; ; note, loves tulips, daisies
+person = Santa, CLAUS, St Nicholas ; 25,12 ; 06,12 ;
+special = IMPORTANT ; 04,07,2030
+special = cafe \\#1 ; 01,01,2030
+"
+		);
+		let after = load_file(&path).unwrap();
+		let mut before: Vec<String> = before.iter().map(Event::uid).collect();
+		let mut after: Vec<String> = after.iter().map(Event::uid).collect();
+		before.sort();
+		after.sort();
+		assert_eq!(
+			before,
+			after
+		);
+	}
+	#[test]
+	fn histogram_counts() {
+		let events = vec![
+			new_event(EventKind::Birthday, date::Fixed::new(3,1,2031), true),
+			new_event(EventKind::Holiday, date::Fixed::new(25,12,2030), true),
+			new_event(EventKind::Special, date::Fixed::new(1,1,2030), false),
+			new_event(EventKind::Special, date::Fixed::new(5,1,2030), false),
+			new_event(EventKind::Special, date::Fixed::new(1,3,2031), false),
+		];
+		let counts = histogram(&events, 2030);
+		assert_eq!(
+			counts,
+			[3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]
+		);
+		let text = render_histogram(&counts);
+		let bars: Vec<usize> = text.lines()
+			.map(|line| line.chars().filter(|c| *c == '#').count())
+			.collect();
+		assert_eq!(
+			bars,
+			counts.to_vec()
+		);
+	}
+	#[test]
+	fn dot_export() {
+		let mut special = new_event(EventKind::Special, date::Fixed::new(1,1,2030), false);
+		special.desc = String::from("say \"hi\"");
+		let events = vec![
+			new_event(EventKind::Birthday, date::Fixed::new(3,1,2030), true),
+			special,
+			new_event(EventKind::Wedding, date::Fixed::new(2,1,2030), true),
+		];
+		let dot = to_dot(&events);
+		let nodes: Vec<&str> = dot.lines().filter(|line| line.contains("label=")).collect();
+		assert_eq!(
+			nodes,
+			vec![
+				"\te0 [label=\"01/01/2030\\nsay \\\"hi\\\"\", fillcolor=cyan];",
+				"\te1 [label=\"02/01/2030\\nevent\", fillcolor=green];",
+				"\te2 [label=\"03/01/2030\\nevent\", fillcolor=red];",
+			]
+		);
+		assert!(dot.contains("e0 -> e1;"));
+		assert!(dot.contains("e1 -> e2;"));
+	}
+	#[test]
+	fn ics_export() {
+		let mut special = new_event(EventKind::Special, date::Fixed::new(31,12,2030), false);
+		special.desc = "a; very, long description ".repeat(4);
+		let events = vec![
+			new_event(EventKind::Birthday, date::Fixed::new(3,1,2030), true),
+			special,
+		];
+		let ics = export_ics(&events);
+		assert!(ics.ends_with("\r\n"));
+		assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+		// unfold then check the properties
+		let unfolded = ics.replace("\r\n ", "");
+		let lines: Vec<&str> = unfolded.split("\r\n").collect();
+		assert_eq!(
+			lines.first(),
+			Some(&"BEGIN:VCALENDAR")
+		);
+		assert_eq!(
+			lines.iter().filter(|line| **line == "BEGIN:VEVENT").count(),
+			2
+		);
+		for expected in [
+			"DTSTART;VALUE=DATE:20300103",
+			"DTEND;VALUE=DATE:20300104",
+			"RRULE:FREQ=YEARLY",
+			"CATEGORIES:birthday",
+			"DTSTART;VALUE=DATE:20301231",
+			"DTEND;VALUE=DATE:20310101",
+			"CATEGORIES:special",
+			"END:VCALENDAR",
+		] {
+			assert!(lines.contains(&expected), "missing {}", expected);
+		}
+		let summary = format!("SUMMARY:{}", "a\\; very\\, long description ".repeat(4));
+		assert!(lines.contains(&summary.as_str()));
+		assert_eq!(
+			lines.iter().filter(|line| line.starts_with("RRULE")).count(),
+			1
+		);
+	}
+	#[test]
+	fn json_export() {
+		let mut special = new_event(EventKind::Special, date::Fixed::new(5,1,2030), false);
+		special.desc = String::from("say \"hi\"\\");
+		let birthday = new_event(EventKind::Birthday, date::Fixed::new(3,1,2030), true);
+		assert_eq!(
+			to_json(&[&birthday, &special], date::Fixed::new(1,1,2030)),
+			concat!(
+				r#"[{"kind":"birthday","date":"2030-01-03","days_until":2,"desc":"event"},"#,
+				r#"{"kind":"special","date":"2030-01-05","days_until":4,"desc":"say \"hi\"\\"}]"#,
+			)
+		);
+		assert_eq!(
+			to_json(&[], date::Fixed::new(1,1,2030)),
+			"[]"
+		);
+	}
+	#[test]
+	fn tsv_export() {
+		let mut special = new_event(EventKind::Special, date::Fixed::new(5,1,2030), false);
+		special.desc = String::from("tab\there");
+		let birthday = new_event(EventKind::Birthday, date::Fixed::new(3,1,2030), true);
+		let tsv = export_tsv(&[birthday, special]);
+		let lines: Vec<&str> = tsv.lines().collect();
+		assert_eq!(
+			lines,
+			vec![
+				"kind\tdate\tdesc",
+				"birthday\t2030-01-03\tevent",
+				"special\t2030-01-05\ttab\\there",
+			]
+		);
+		assert_eq!(
+			export_tsv(&[]),
+			"kind\tdate\tdesc\n"
+		);
+	}
+	#[test]
+	fn project_skip_leap_day() {
+		let events = vec![
+			new_event(EventKind::Birthday, date::Fixed::new(29,2,2024), true),
+			new_event(EventKind::Holiday, date::Fixed::new(25,12,2024), true),
+			new_event(EventKind::Special, date::Fixed::new(1,1,2024), false),
+		];
+		let (projected, skipped) = project(&events, 2023, date::LeapDayPolicy::Skip);
+		let dates: Vec<date::Fixed> = projected.iter().map(|e| e.date).collect();
+		assert_eq!(
+			dates,
+			vec![date::Fixed::new(25,12,2023)]
+		);
+		assert_eq!(
+			skipped.len(),
+			1
+		);
+		assert_eq!(
+			skipped_warning(2023, &skipped),
+			Some(String::from("warning: 2023 is not leap, skipped the 29/02 events: event"))
+		);
+		let (projected, skipped) = project(&events, 2023, date::LeapDayPolicy::Feb28);
+		assert_eq!(
+			projected.len(),
+			2
+		);
+		assert_eq!(
+			skipped_warning(2023, &skipped),
+			None
+		);
+	}
+	#[test]
+	fn project_moving_holiday() {
+		let events = extract("holiday = Good Friday ; easter, -2").unwrap();
+		let (projected, _) = project(&events, 2024, date::LeapDayPolicy::Feb28);
+		assert_eq!(
+			projected.iter().map(|e| e.date).collect::<Vec<_>>(),
+			vec![date::Fixed::new(29,3,2024)]
+		);
+		assert_eq!(
+			histogram(&events, 2025)[3],
+			1
+		);
+		assert_eq!(
+			future_occurrences(&events, date::Fixed::new(1,4,2024), 2).iter().map(|e| e.date).collect::<Vec<_>>(),
+			vec![date::Fixed::new(18,4,2025), date::Fixed::new(3,4,2026)]
+		);
+	}
+	#[test]
+	fn project_nth_weekday_holiday() {
+		let events = extract("holiday = Thanksgiving ; nth, 4, Thu, 11").unwrap();
+		for (year, expected) in [
+			(2024, date::Fixed::new(28,11,2024)),
+			(2025, date::Fixed::new(27,11,2025)),
+		] {
+			assert_eq!(
+				project(&events, year, date::LeapDayPolicy::Feb28).0.iter().map(|e| e.date).collect::<Vec<_>>(),
+				vec![expected]
+			);
+		}
+		let events = extract("holiday = fifth Monday ; nth, 5, Mon, 2").unwrap();
+		assert!(project(&events, 2025, date::LeapDayPolicy::Feb28).0.is_empty());
+		assert_eq!(
+			future_occurrences(&events, date::Fixed::new(1,1,2025), 1)[0].date,
+			date::Fixed::new(29,2,2044)
+		);
+	}
+	#[test]
+	fn dashboard_soonest() {
+		let mut events = vec![
+			new_event(EventKind::Birthday, date::Fixed::new(3,1,2030), true),
+			new_event(EventKind::Birthday, date::Fixed::new(3,1,2030), true),
+			new_event(EventKind::Birthday, date::Fixed::new(9,1,2030), true),
+			new_event(EventKind::Holiday, date::Fixed::new(1,1,2030), false),
+			new_event(EventKind::Special, date::Fixed::new(31,12,2029), false),
+		];
+		events[1].desc = String::from("other");
+		assert_eq!(
+			dashboard(&events, date::Fixed::new(1,1,2030)),
+			vec![
+				(EventKind::Birthday, Some((date::Fixed::new(3,1,2030), 2, String::from("event, other")))),
+				(EventKind::SaintDay, None),
+				(EventKind::Wedding, None),
+				(EventKind::Holiday, Some((date::Fixed::new(1,1,2030), 0, String::from("event")))),
+				(EventKind::Special, None),
+				(EventKind::Countdown, None),
+				(EventKind::Weekly, None),
+			]
+		);
+	}
+	#[test]
+	fn upcoming_lead() {
+		let now = date::Fixed::new(1,1,2030);
+		let mut events = vec![
+			new_event(EventKind::Special, date::Fixed::new(4,1,2030), false),
+			new_event(EventKind::Special, date::Fixed::new(4,1,2030), false),
+			new_event(EventKind::Holiday, date::Fixed::new(10,1,2030), true),
+			new_event(EventKind::Holiday, date::Fixed::new(31,12,2029), true),
+		];
+		events[0].lead = Some(5);
+		events[0].desc = String::from("announced");
+		events[2].lead = Some(5);
+		events[3].lead = Some(5);
+		let upcoming = get_upcoming(&events, now);
+		assert_eq!(
+			upcoming.len(),
+			1
+		);
+		assert_eq!(
+			upcoming[0].desc,
+			"announced"
+		);
+		assert_eq!(
+			now.to(upcoming[0].date),
+			3
+		);
+	}
+	#[test]
+	fn join_limited_more() {
+		let items: Vec<String> = ["a", "b", "c", "d"].iter().map(|item| String::from(*item)).collect();
+		assert_eq!(
+			join_limited(&items, 2),
+			"a, b (+2 more)"
+		);
+		assert_eq!(
+			join_limited(&items, 4),
+			"a, b, c, d"
+		);
+		assert_eq!(
+			join_limited(&items, 0),
+			"a, b, c, d"
+		);
+	}
+	#[test]
+	fn kind_from_name() {
+		for kind in KIND_LIST {
+			assert!(EventKind::try_from(kind.name()).unwrap() == kind);
+		}
+		assert!(EventKind::try_from(" saint-day ").unwrap() == EventKind::SaintDay);
+		assert!(EventKind::try_from("wedding_anniversary").unwrap() == EventKind::Wedding);
+		assert!(EventKind::try_from("HOLIDAY").unwrap() == EventKind::Holiday);
+		assert!(
+			EventKind::try_from("person")
+			.is_err_and(|e| e.what().starts_with("no EventKind matched") && e.what().contains("birthday"))
+		);
+		assert_eq!(
+			EventKind::try_from("chore").unwrap_err().what(),
+			"no EventKind matched, expected one of: birthday, saint day, wedding anniversary, holiday, special, countdown, weekly"
+		);
+	}
+	#[test]
+	fn split_quoted_slots() {
+		assert_eq!(
+			split_quoted(" \"Meeting; with; boss\" ; 1,1,2025", ';'),
+			Ok(vec![" \"Meeting; with; boss\" ", " 1,1,2025"])
+		);
+		assert_eq!(
+			split_quoted("a ; note, \"b; c\"", ';'),
+			Ok(vec!["a ", " note, \"b; c\""])
+		);
+		assert!(
+			split_quoted("\"a ; b", ';')
+			.is_err()
+		);
+		assert_eq!(
+			unquote(" \" padded \" "),
+			" padded "
+		);
+		assert_eq!(
+			unquote(" plain "),
+			"plain"
+		);
+	}
+	#[test]
+	fn extract_quoted() {
+		let events = extract("special = \"Meeting; with; boss\" ; 1,1,2025").unwrap();
+		assert_eq!(
+			events[0].desc,
+			"Meeting; with; boss"
+		);
+		let events = extract("holiday = \"a = b; c\" ; 1,5 ; lead, 3").unwrap();
+		assert_eq!(
+			events[0].desc,
+			"a = b; c"
+		);
+		let events = extract("countdown = \"T; minus\" ; 1,1,2030").unwrap();
+		assert_eq!(
+			events[0].desc,
+			"T; minus"
+		);
+		let events = extract("person = Jane, DOE, \"J; D\" ; 1,1 ; ; ; note, \"tulips; daisies\"").unwrap();
+		assert_eq!(
+			(events[0].desc.as_str(), events[0].note.as_deref()),
+			("J; D", Some("tulips; daisies"))
+		);
+		for line in [
+			"special = \"Meeting; with; boss\" ; 01,01,2025",
+			"holiday = \"a = b; c\" ; 01,05 ; lead, 3",
+			"countdown = \"T; minus\" ; 01,01,2030",
+			"person = Jane, DOE, \"J; D\" ; 01,01 ; ; ; note, \"tulips; daisies\"",
+		] {
+			assert_eq!(
+				Entry::try_from(line).unwrap().to_string(),
+				line
+			);
+		}
+		assert!(
+			extract("special = \"Meeting; with boss ; 1,1,2025")
+			.is_err()
+		);
+	}
+	#[test]
+	fn extract_keywords() {
+		assert!(extract("person = a,, ; 1,1 ; 2,2 ;").unwrap().len() == 2);
+		assert!(extract("holiday = a ; 1,1").unwrap()[0].kind == EventKind::Holiday);
+		assert!(extract("Special = a ; 1,1,2030").unwrap()[0].kind == EventKind::Special);
+		assert!(extract("countdown = a ; 1,1,2030").unwrap()[0].kind == EventKind::Countdown);
+		assert!(
+			extract("birthday = a ; 1,1")
+			.is_err()
+		);
+		assert!(
+			extract("unknown = a ; 1,1")
+			.is_err_and(|e| e.what().starts_with("no EventKind matched"))
+		);
+	}
+	#[test]
+	fn extract_keywords_case() {
+		let expected = extract("holiday = Christmas ; 25,12").unwrap();
+		for line in ["Holiday = Christmas ; 25,12", "HOLIDAY = Christmas ; 25,12", "  hOliDay\t= Christmas ; 25,12"] {
+			let events = extract(line).unwrap();
+			assert_eq!(
+				events,
+				expected
+			);
+			assert_eq!(
+				events[0].kind,
+				EventKind::Holiday
+			);
+		}
+		assert_eq!(
+			extract("PERSON = a,, ; 1,1 ; 2,2 ;").unwrap(),
+			extract("person = a,, ; 1,1 ; 2,2 ;").unwrap()
+		);
+		assert_eq!(
+			EventKind::try_from(" Saint   Day "),
+			Ok(EventKind::SaintDay)
+		);
+	}
+	#[test]
+	fn all_upcoming_sorted() {
+		let events = vec![
+			new_event(EventKind::Special, date::Fixed::new(2,1,9999), false),
+			new_event(EventKind::Holiday, date::Fixed::new(1,1,9999), true),
+			new_event(EventKind::Special, date::Fixed::new(1,1,2000), false),
+			new_event(EventKind::Birthday, date::Fixed::new(2,1,9999), true),
+			new_event(EventKind::Countdown, date::Fixed::new(1,1,9998), false),
+		];
+		let upcoming = all_upcoming(&events);
+		assert_eq!(
+			upcoming.len(),
+			4
+		);
+		assert!(upcoming.windows(2).all(|pair| pair[0].date <= pair[1].date));
+		assert!(upcoming[2].kind == EventKind::Birthday);
+		assert!(upcoming[3].kind == EventKind::Special);
+	}
+	#[test]
+	fn most_imminent_tie() {
+		let events = vec![
+			new_event(EventKind::Special, date::Fixed::new(1,1,9999), false),
+			new_event(EventKind::Holiday, date::Fixed::new(2,1,9999), true),
+			new_event(EventKind::Birthday, date::Fixed::new(1,1,9999), true),
+			new_event(EventKind::Special, date::Fixed::new(1,1,2000), false),
+		];
+		assert_eq!(
+			most_imminent(&events).map(|e| e.kind),
+			Some(EventKind::Birthday)
+		);
+	}
+	#[test]
+	fn most_imminent_empty() {
+		assert!(most_imminent(&[]).is_none());
+		let events = vec![new_event(EventKind::Special, date::Fixed::new(1,1,2000), false)];
+		assert!(most_imminent(&events).is_none());
+	}
+	#[test]
+	fn expand_spans() {
+		let line = "holiday = Summer ; 1,7,2030 ; 31,8,2030";
+		assert_eq!(
+			extract(line).unwrap().len(),
+			62
+		);
+		let events = Entry::try_from(line).unwrap().into_events_in(&Context{ expand_spans: false, ..Context::default() });
+		assert_eq!(
+			events.len(),
+			1
+		);
+		assert_eq!(
+			events[0].desc,
+			"Summer (62 days)"
+		);
+	}
+	#[test]
+	fn bridge_thursday() {
+		assert_eq!(
+			new_holiday(date::Fixed::new(25,12,2025)).bridge_note(),
+			Some(String::from("bridge: take Friday 26/12 off"))
+		);
+	}
+	#[test]
+	fn bridge_tuesday() {
+		assert_eq!(
+			new_holiday(date::Fixed::new(25,12,2029)).bridge_note(),
+			Some(String::from("bridge: take Monday 24/12 off"))
+		);
+	}
+	#[test]
+	fn bridge_wednesday() {
+		assert_eq!(
+			new_holiday(date::Fixed::new(25,12,2024)).bridge_note(),
+			None
+		);
+	}
+	#[test]
+	fn bridge_not_holiday() {
+		let mut event = new_holiday(date::Fixed::new(25,12,2025));
+		event.kind = EventKind::Special;
+		assert_eq!(
+			event.bridge_note(),
+			None
+		);
+	}
+} // mod test
@@ -1,130 +1,309 @@
 use super::*;
 
+pub mod saints;
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct Person {
 	name: String,
-	birthday: Option<date::AnyDate>,
+	// first_name, last_name & nickname as written
+	full_name: [String; 3],
+	// several dates may be given, separated by '|'
+	birthday: Vec<date::AnyDate>,
 	saint_day: Option<date::Recurring>,
-	wedding_day: Option<date::AnyDate>,
+	// several dates may be given, separated by '|'
+	wedding_day: Vec<date::AnyDate>,
+	note: Option<String>,
+	// also celebrate the birthdays six months later
+	half: bool,
 }
 
 fn parse_name(value: &str) -> Result<String> {
 	let mut iter = value.split(',');
 	let Some(first_name) = iter.next() else {
-		return Err("missing 'first_name' slot");
+		return Err(Error::Parse{ what: "missing 'first_name' slot" });
 	};
 	let Some(last_name) = iter.next() else {
-		return Err("missing 'last_name' slot");
+		return Err(Error::Parse{ what: "missing 'last_name' slot" });
 	};
 	let Some(nickname) = iter.next() else {
-		return Err("missing 'nickname' slot");
+		return Err(Error::Parse{ what: "missing 'nickname' slot" });
 	};
 	if iter.next().is_some() {
-		return Err("extra ',' found");
+		return Err(Error::Parse{ what: "extra ',' found" });
 	}
-	let first_name = first_name.trim();
-	let last_name = last_name.trim();
-	let nickname = nickname.trim();
+	let first_name = unquote(first_name);
+	let last_name = unquote(last_name);
+	let nickname = unquote(nickname);
 	if !nickname.is_empty() {
-		return Ok(format!("{}", nickname));
+		return Ok(String::from(nickname));
 	}
 	if first_name.is_empty() {
-		return Err("at least first_name or nickname must be provided");
+		return Err(Error::Parse{ what: "at least first_name or nickname must be provided" });
 	}
 	if last_name.is_empty() {
-		return Ok(format!("{}", first_name));
+		return Ok(String::from(first_name));
 	}
 	Ok(format!("{} {}", first_name, last_name))
 }
 
+// return the trimmed first_name, last_name & nickname
+// the slots are expected to be validated by parse_name
+fn parse_full_name(value: &str) -> [String; 3] {
+	let mut iter = value.split(',').map(|slot| String::from(unquote(slot)));
+	[
+		iter.next().unwrap_or_default(),
+		iter.next().unwrap_or_default(),
+		iter.next().unwrap_or_default(),
+	]
+}
+
+// parse a slot holding zero, one or several '|' separated dates
+fn parse_dates(value: &str) -> Result<Vec<date::AnyDate>> {
+	if value.trim().is_empty() {
+		return Ok(Vec::new());
+	}
+	value.split('|').map(date::AnyDate::try_from).collect()
+}
+
+// return the .rce representation of dates parsed by parse_dates
+fn dates_to_rce(dates: &[date::AnyDate]) -> String {
+	let dates: Vec<String> = dates.iter().map(|date| date.to_rce()).collect();
+	dates.join("|")
+}
+
+impl Person {
+
+	// return a person known by <name> only, add the dates with the with_* methods
+	pub fn new(name: String) -> Self {
+		Person{
+			name: name.clone(),
+			full_name: [String::new(), String::new(), name],
+			birthday: Vec::new(),
+			saint_day: None,
+			wedding_day: Vec::new(),
+			note: None,
+			half: false,
+		}
+	}
+
+	// add a birthday, may be called several times
+	pub fn with_birthday(mut self, birthday: date::AnyDate) -> Self {
+		self.birthday.push(birthday);
+		self
+	}
+
+	pub fn with_saint_day(mut self, saint_day: date::Recurring) -> Self {
+		self.saint_day = Some(saint_day);
+		self
+	}
+
+	// add a wedding day, may be called several times
+	pub fn with_wedding_day(mut self, wedding_day: date::AnyDate) -> Self {
+		self.wedding_day.push(wedding_day);
+		self
+	}
+
+	// return the name used in the event descriptions
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	// return the first birthday, see birthdays() for all of them
+	pub fn birthday(&self) -> Option<&date::AnyDate> {
+		self.birthday.first()
+	}
+
+	pub fn birthdays(&self) -> &[date::AnyDate] {
+		&self.birthday
+	}
+
+	pub fn saint_day(&self) -> Option<&date::Recurring> {
+		self.saint_day.as_ref()
+	}
+
+	// return the first wedding day, see wedding_days() for all of them
+	pub fn wedding_day(&self) -> Option<&date::AnyDate> {
+		self.wedding_day.first()
+	}
+
+	pub fn wedding_days(&self) -> &[date::AnyDate] {
+		&self.wedding_day
+	}
+}
+
 impl TryFrom<&str> for Person {
 	type Error = Error;
 	fn try_from(value: &str) -> Result<Self> {
-		let mut iter = value.split(';');
+		Person::parse(value, DEFAULT_SEPARATOR)
+	}
+}
+
+impl Person {
+
+	// same as try_from, the slots being separated by <separator>
+	pub fn parse(value: &str, separator: char) -> Result<Self> {
+		Ok(Person::parse_lenient(value, separator)?.0)
+	}
+
+	// same as parse, along with the problems that did not prevent parsing
+	// e.g. an "auto" saint day unknown for the first name, skipped
+	pub fn parse_lenient(value: &str, separator: char) -> Result<(Self, Vec<Error>)> {
+		let mut warnings: Vec<Error> = Vec::new();
+		let mut iter = split_quoted(value, separator)?.into_iter();
 		let Some(name) = iter.next() else {
-			return Err("missing 'name' slot");
+			return Err(Error::Parse{ what: "missing 'name' slot" });
 		};
 		let Some(birthday) = iter.next() else {
-			return Err("missing 'birthday' slot");
+			return Err(Error::Parse{ what: "missing 'birthday' slot" });
 		};
 		let Some(saint_day) = iter.next() else {
-			return Err("missing 'saint_day' slot");
+			return Err(Error::Parse{ what: "missing 'saint_day' slot" });
 		};
 		let Some(wedding_day) = iter.next() else {
-			return Err("missing 'wedding_day' slot");
+			return Err(Error::Parse{ what: "missing 'wedding_day' slot" });
 		};
-		if iter.next().is_some() {
-			return Err("extra ';' found");
+		let mut note = None;
+		let mut half = false;
+		for slot in iter {
+			match parse_option(slot) {
+				("note", Some(value)) => note = Some(String::from(unquote(value))),
+				("note", None) => return Err(Error::Parse{ what: "missing note value" }),
+				("half", None) => half = true,
+				_ => return Err(Error::Parse{ what: "extra ';' found" }),
+			}
 		}
+		let full_name = parse_full_name(name);
+		let first_name = full_name[0].as_str();
 		let name = parse_name(name)?;
-		let birthday = if birthday.trim().is_empty() {
-			None
-		} else {
-			Some(date::AnyDate::try_from(birthday)?)
-		};
+		let birthday = parse_dates(birthday)?;
 		let saint_day = if saint_day.trim().is_empty() {
 			None
+		} else if saint_day.trim() == "auto" {
+			let found = saints::lookup(first_name);
+			if found.is_none() {
+				warnings.push(Error::OutOfRange{ what: "no saint day known for the first name, skipped" });
+			}
+			found
 		} else {
 			Some(date::Recurring::try_from(saint_day)?)
 		};
-		let wedding_day = if wedding_day.trim().is_empty() {
-			None
-		} else {
-			Some(date::AnyDate::try_from(wedding_day)?)
-		};
-		Ok(Person{name, birthday, saint_day, wedding_day})
+		let wedding_day = parse_dates(wedding_day)?;
+		Ok((Person{name, full_name: full_name.clone(), birthday, saint_day, wedding_day, note, half}, warnings))
+	}
+}
+
+// return "1 year" or "<count> years"
+fn years(count: i32) -> String {
+	match count {
+		1 => String::from("1 year"),
+		_ => format!("{} years", count),
 	}
 }
 
-fn get_next_and_diff(date: date::AnyDate) -> (date::Fixed, Option<i32>) {
+fn get_next_and_diff(date: date::AnyDate, today: date::Fixed, policy: date::LeapDayPolicy) -> (date::Fixed, Option<i32>) {
 	match date {
 		date::AnyDate::Recurring(recurring) => {
 			let fixed = date::Fixed::from(recurring);
-			let next = fixed.next_match();
+			let next = fixed.next_match_on(today, policy);
 			(next, None)
 		},
-		date::AnyDate::Fixed(fixed) => {
-			let next = fixed.next_match();
-			let diff = fixed.year_diff(next);
-			(next, Some(diff))
+		// no age if it does not fit, e.g. born in the first representable year
+		date::AnyDate::Fixed(fixed) => match fixed.next_anniversary_on(today, policy) {
+			Ok((next, age)) => (next, Some(age)),
+			Err(_) => (fixed.next_match_on(today, policy), None),
 		},
 	}
 }
 
+impl fmt::Display for Person {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let [first_name, last_name, nickname] = &self.full_name;
+		write!(f, "person = {}, {}, {} ;", quote(first_name), quote(last_name), quote(nickname))?;
+		match self.birthday.is_empty() {
+			true => write!(f, " ;")?,
+			false => write!(f, " {} ;", dates_to_rce(&self.birthday))?,
+		};
+		match self.saint_day {
+			None => write!(f, " ;")?,
+			Some(saint_day) => write!(f, " {} ;", saint_day.to_rce())?,
+		};
+		if !self.wedding_day.is_empty() {
+			write!(f, " {}", dates_to_rce(&self.wedding_day))?;
+		}
+		if let Some(note) = &self.note {
+			write!(f, " ; note, {}", quote(note))?;
+		}
+		if self.half {
+			write!(f, " ; half")?;
+		}
+		Ok(())
+	}
+}
+
 impl IntoEvents for Person {
-	fn into_events(self) -> Vec<Event> {
+	fn into_events_in(self, context: &Context) -> Vec<Event> {
 		let mut vec: Vec<Event> = Vec::new();
-		if let Some(birthday) = self.birthday {
-			let (date, age) = get_next_and_diff(birthday);
+		for birthday in self.birthday {
+			let (date, age) = get_next_and_diff(birthday, context.today, context.leap_day);
 			let desc = match age {
-				None => format!("{}", self.name),
-				Some(age) => format!("{} (age {})", self.name, age),
+				None => self.name.clone(),
+				Some(age) if date == context.today => format!("{} (turns {} old today)", self.name, years(age)),
+				Some(age) => format!("{} (turns {} old on {})", self.name, years(age), date),
 			};
 			let event = Event {
 				kind: EventKind::Birthday,
-				date: date,
-				desc: desc,
+				date,
+				desc,
+				recurring: Some(birthday.recurring()),
+				rule: None,
+				note: self.note.clone(),
+				lead: None,
+				tags: Vec::new(),
 			};
 			vec.push(event);
+			if self.half {
+				let half = birthday.recurring().add_months(6);
+				let event = Event {
+					kind: EventKind::Birthday,
+					date: date::Fixed::from(half).next_match_on(context.today, context.leap_day),
+					desc: format!("half-birthday of {}", self.name),
+					recurring: Some(half),
+					rule: None,
+					note: None,
+					lead: None,
+					tags: Vec::new(),
+				};
+				vec.push(event);
+			}
 		};
 		if let Some(saint_day) = self.saint_day {
 			let event = Event {
 				kind: EventKind::SaintDay,
-				date: date::Fixed::from(saint_day).next_match(),
+				date: date::Fixed::from(saint_day).next_match_on(context.today, context.leap_day),
 				desc: self.name.clone(),
+				recurring: Some(saint_day),
+				rule: None,
+				note: None,
+				lead: None,
+				tags: Vec::new(),
 			};
 			vec.push(event);
 		};
-		if let Some(wedding_day) = self.wedding_day {
-			let (date, year) = get_next_and_diff(wedding_day);
+		for wedding_day in self.wedding_day {
+			let (date, year) = get_next_and_diff(wedding_day, context.today, context.leap_day);
 			let desc = match year {
-				None => format!("{}", self.name),
-				Some(year) => format!("{} (year {})", self.name, year),
+				None => self.name.clone(),
+				Some(year) => format!("{} ({})", self.name, years(year)),
 			};
 			let event = Event {
 				kind: EventKind::Wedding,
-				date: date,
-				desc: desc,
+				date,
+				desc,
+				recurring: Some(wedding_day.recurring()),
+				rule: None,
+				note: self.note.clone(),
+				lead: None,
+				tags: Vec::new(),
 			};
 			vec.push(event);
 		};
@@ -194,16 +373,19 @@ mod test {
 	}
 
 	fn new_person(
-		name: &str,
+		full_name: &str,
 		birthday: Option<date::AnyDate>,
 		saint_day: Option<date::Recurring>,
 		wedding_day: Option<date::AnyDate>
 	) -> Person {
 		Person{
-			name: String::from(name),
-			birthday: birthday,
-			saint_day: saint_day,
-			wedding_day: wedding_day,
+			name: parse_name(full_name).unwrap(),
+			full_name: parse_full_name(full_name),
+			birthday: birthday.into_iter().collect(),
+			saint_day,
+			wedding_day: wedding_day.into_iter().collect(),
+			note: None,
+			half: false,
 		}
 	}
 
@@ -212,7 +394,7 @@ mod test {
 		assert_eq!(
 			Person::try_from("a,b,c;1,1,1;2,2;3,3,3").unwrap(),
 			new_person(
-				"c",
+				"a,b,c",
 				Some(date::AnyDate::Fixed(date::Fixed::new(1,1,1))),
 				Some(date::Recurring::new(2,2)),
 				Some(date::AnyDate::Fixed(date::Fixed::new(3,3,3)))
@@ -224,7 +406,7 @@ mod test {
 		assert_eq!(
 			Person::try_from("a,b,;1,1,1;2,2;3,3,3").unwrap(),
 			new_person(
-				"a b",
+				"a,b,",
 				Some(date::AnyDate::Fixed(date::Fixed::new(1,1,1))),
 				Some(date::Recurring::new(2,2)),
 				Some(date::AnyDate::Fixed(date::Fixed::new(3,3,3)))
@@ -236,7 +418,7 @@ mod test {
 		assert_eq!(
 			Person::try_from(" a , b , c ; 1 , 1 , 1 ; 2 , 2 ; 3 , 3 , 3 ").unwrap(),
 			new_person(
-				"c",
+				"a,b,c",
 				Some(date::AnyDate::Fixed(date::Fixed::new(1,1,1))),
 				Some(date::Recurring::new(2,2)),
 				Some(date::AnyDate::Fixed(date::Fixed::new(3,3,3)))
@@ -248,7 +430,7 @@ mod test {
 		assert_eq!(
 			Person::try_from("a,b,c;;2,2;3,3,3").unwrap(),
 			new_person(
-				"c",
+				"a,b,c",
 				None,
 				Some(date::Recurring::new(2,2)),
 				Some(date::AnyDate::Fixed(date::Fixed::new(3,3,3)))
@@ -260,7 +442,7 @@ mod test {
 		assert_eq!(
 			Person::try_from("a,b,c;1,1;2,2;3,3,3").unwrap(),
 			new_person(
-				"c",
+				"a,b,c",
 				Some(date::AnyDate::Recurring(date::Recurring::new(1,1))),
 				Some(date::Recurring::new(2,2)),
 				Some(date::AnyDate::Fixed(date::Fixed::new(3,3,3)))
@@ -272,7 +454,7 @@ mod test {
 		assert_eq!(
 			Person::try_from("a,b,c;1,1,1;;3,3,3").unwrap(),
 			new_person(
-				"c",
+				"a,b,c",
 				Some(date::AnyDate::Fixed(date::Fixed::new(1,1,1))),
 				None,
 				Some(date::AnyDate::Fixed(date::Fixed::new(3,3,3)))
@@ -284,7 +466,7 @@ mod test {
 		assert_eq!(
 			Person::try_from("a,b,c;1,1,1;2,2;").unwrap(),
 			new_person(
-				"c",
+				"a,b,c",
 				Some(date::AnyDate::Fixed(date::Fixed::new(1,1,1))),
 				Some(date::Recurring::new(2,2)),
 				None
@@ -296,7 +478,7 @@ mod test {
 		assert_eq!(
 			Person::try_from("a,b,c;1,1,1;2,2;3,3").unwrap(),
 			new_person(
-				"c",
+				"a,b,c",
 				Some(date::AnyDate::Fixed(date::Fixed::new(1,1,1))),
 				Some(date::Recurring::new(2,2)),
 				Some(date::AnyDate::Recurring(date::Recurring::new(3,3)))
@@ -307,7 +489,276 @@ mod test {
 	fn person_parse_empty() {
 		assert_eq!(
 			Person::try_from("a,b,c;;;").unwrap(),
-			new_person("c",None,None,None)
+			new_person("a,b,c",None,None,None)
+		);
+	}
+	#[test]
+	fn person_parse_auto_saint() {
+		assert_eq!(
+			Person::try_from("Nicolas,b,c;;auto;").unwrap(),
+			new_person("Nicolas,b,c",None,Some(date::Recurring::new(6,12)),None)
+		);
+	}
+	#[test]
+	fn person_parse_auto_saint_unknown() {
+		assert_eq!(
+			Person::try_from("Rustacean,b,c;;auto;").unwrap(),
+			new_person("Rustacean,b,c",None,None,None)
+		);
+		assert_eq!(
+			Person::parse_lenient("Rustacean,b,c;;auto;", ';').unwrap().1,
+			vec![Error::OutOfRange{ what: "no saint day known for the first name, skipped" }]
+		);
+		assert!(Person::parse_lenient("Nicolas,b,c;;auto;", ';').unwrap().1.is_empty());
+	}
+	#[test]
+	fn person_parse_note() {
+		let mut expected = new_person("a,b,c",Some(date::AnyDate::Recurring(date::Recurring::new(1,1))),None,None);
+		expected.note = Some(String::from("buy flowers, chocolate"));
+		assert_eq!(
+			Person::try_from("a,b,c;1,1;;; note , buy flowers, chocolate ").unwrap(),
+			expected
+		);
+		assert!(
+			Person::try_from("a,b,c;1,1;;;note")
+			.is_err()
+		);
+	}
+	#[test]
+	fn person_note_shown_or_hidden() {
+		let events = Person::try_from("a,b,c;1,1;;;note,flowers").unwrap().into_events();
+		assert_eq!(
+			events[0].full_desc(true),
+			"c [flowers]"
+		);
+		assert_eq!(
+			events[0].full_desc(false),
+			"c"
+		);
+	}
+	#[test]
+	fn person_turns() {
+		let now = date::Fixed::now();
+		let events = Person::try_from("a,b,c;1,1,1990;;").unwrap().into_events();
+		let next = events[0].date;
+		let age = next.year() - 1990;
+		assert!(age >= now.year() - 1990);
+		let expected = match next == now {
+			true => format!("c (turns {} years old today)", age),
+			false => format!("c (turns {} years old on {})", age, next),
+		};
+		assert_eq!(
+			events[0].desc,
+			expected
+		);
+		let today = format!("a,b,c;{},{},1990;;", now.recurring().day(), now.recurring().month());
+		if now.recurring() != date::Recurring::new(29,2) {
+			let events = Person::try_from(today.as_str()).unwrap().into_events();
+			assert_eq!(
+				events[0].desc,
+				format!("c (turns {} years old today)", now.year() - 1990)
+			);
+		}
+	}
+	#[test]
+	fn person_years_plural() {
+		assert_eq!(
+			(years(0), years(1), years(2)),
+			(String::from("0 years"), String::from("1 year"), String::from("2 years"))
+		);
+		let desc = |today, line: &str, kind| {
+			let events = Person::try_from(line).unwrap().into_events_in(&Context{ today, ..Context::default() });
+			events.into_iter().find(|e| e.kind == kind).unwrap().desc
+		};
+		let wedding = "a,, ; ; ; 1,6,2029";
+		let birthday = "a,, ; 1,6,2029 ; ;";
+		for (today, expected) in [
+			(date::Fixed::new(1,6,2029), ["a (0 years)", "a (turns 0 years old today)"]),
+			(date::Fixed::new(1,6,2030), ["a (1 year)", "a (turns 1 year old today)"]),
+			(date::Fixed::new(1,5,2031), ["a (2 years)", "a (turns 2 years old on 01/06/2031)"]),
+		] {
+			assert_eq!(
+				[desc(today, wedding, EventKind::Wedding), desc(today, birthday, EventKind::Birthday)],
+				expected
+			);
+		}
+	}
+	#[test]
+	fn person_several_dates() {
+		let person = Person::try_from("a,b,c;1,1,1990;;3,3,2010|4,4").unwrap();
+		assert_eq!(
+			person.wedding_day,
+			vec![
+				date::AnyDate::Fixed(date::Fixed::new(3,3,2010)),
+				date::AnyDate::Recurring(date::Recurring::new(4,4)),
+			]
+		);
+		assert_eq!(
+			person.to_string(),
+			"person = a, b, c ; 01,01,1990 ; ; 03,03,2010|04,04"
+		);
+		let events = person.into_events();
+		let weddings: Vec<date::Recurring> = events.iter()
+			.filter(|e| e.kind == EventKind::Wedding)
+			.map(|e| e.date.recurring())
+			.collect();
+		assert_eq!(
+			weddings,
+			vec![date::Recurring::new(3,3), date::Recurring::new(4,4)]
+		);
+		assert!(
+			Person::try_from("a,b,c;1,1|;;")
+			.is_err()
+		);
+	}
+	#[test]
+	fn person_builder() {
+		let built = Person::new(String::from("St Nicholas"))
+			.with_birthday(date::AnyDate::Recurring(date::Recurring::new(25,12)))
+			.with_saint_day(date::Recurring::new(6,12))
+			.with_wedding_day(date::AnyDate::Fixed(date::Fixed::new(1,1,2000)))
+			.with_wedding_day(date::AnyDate::Fixed(date::Fixed::new(2,2,2010)));
+		let parsed = Person::try_from(",,St Nicholas;25,12;6,12;1,1,2000|2,2,2010").unwrap();
+		assert_eq!(
+			built,
+			parsed
+		);
+		let built: Vec<(EventKind, date::Fixed, String)> = built.into_events().into_iter().map(|e| (e.kind, e.date, e.desc)).collect();
+		let parsed: Vec<(EventKind, date::Fixed, String)> = parsed.into_events().into_iter().map(|e| (e.kind, e.date, e.desc)).collect();
+		assert_eq!(
+			built.len(),
+			4
+		);
+		assert_eq!(
+			built,
+			parsed
+		);
+	}
+	#[test]
+	fn person_getters() {
+		let person = Person::try_from("Jane, DOE, ; 14,3,1990 ; 1,4 ; 12,6,2015|20,9").unwrap();
+		assert_eq!(
+			person.name(),
+			"Jane DOE"
+		);
+		assert_eq!(
+			person.birthday(),
+			Some(&date::AnyDate::Fixed(date::Fixed::new(14,3,1990)))
+		);
+		assert_eq!(
+			person.saint_day(),
+			Some(&date::Recurring::new(1,4))
+		);
+		assert_eq!(
+			person.wedding_day(),
+			Some(&date::AnyDate::Fixed(date::Fixed::new(12,6,2015)))
+		);
+		assert_eq!(
+			person.wedding_days(),
+			&[
+				date::AnyDate::Fixed(date::Fixed::new(12,6,2015)),
+				date::AnyDate::Recurring(date::Recurring::new(20,9)),
+			]
+		);
+		let person = Person::try_from("Jane, , ; ; ;").unwrap();
+		assert_eq!(
+			person.birthday(),
+			None
+		);
+		assert!(person.birthdays().is_empty());
+		assert_eq!(
+			person.saint_day(),
+			None
+		);
+		assert_eq!(
+			person.wedding_day(),
+			None
+		);
+	}
+	#[test]
+	fn person_leap_day_age() {
+		let desc = |today| {
+			Person::try_from("Leap, , ; 29,2,2000 ; ;").unwrap().into_events_in(&Context{ today, ..Context::default() })[0].desc.clone()
+		};
+		assert_eq!(
+			desc(date::Fixed::new(28,2,2023)),
+			"Leap (turns 23 years old today)"
+		);
+		assert_eq!(
+			desc(date::Fixed::new(1,3,2023)),
+			"Leap (turns 24 years old on 29/02/2024)"
+		);
+		assert_eq!(
+			desc(date::Fixed::new(29,2,2024)),
+			"Leap (turns 24 years old today)"
+		);
+		assert_eq!(
+			desc(date::Fixed::new(1,1,2025)),
+			"Leap (turns 25 years old on 28/02/2025)"
+		);
+	}
+	#[test]
+	fn person_min_year_no_age() {
+		let today = date::Fixed::new(1,6,2024);
+		let events = Person::try_from("Old, , ; 1,1,-2147483648 ; ;").unwrap().into_events_in(&Context{ today, ..Context::default() });
+		assert_eq!(
+			(events[0].date, events[0].desc.as_str()),
+			(date::Fixed::new(1,1,2025), "Old")
+		);
+	}
+	#[test]
+	fn person_half_birthday() {
+		let person = Person::try_from("a,b,c;15,11,1990;;;half").unwrap();
+		assert_eq!(
+			person.to_string(),
+			"person = a, b, c ; 15,11,1990 ; ; ; half"
+		);
+		let events = person.into_events();
+		assert_eq!(
+			events.len(),
+			2
+		);
+		assert_eq!(
+			events[1].desc,
+			"half-birthday of c"
+		);
+		assert_eq!(
+			events[1].recurring,
+			Some(date::Recurring::new(15,5))
+		);
+		let events = Person::try_from("a,b,c;31,8;;;half").unwrap().into_events();
+		assert_eq!(
+			events[1].recurring,
+			Some(date::Recurring::new(29,2))
+		);
+		assert!([28, 29].contains(&events[1].date.recurring().day()));
+		assert!(
+			Person::try_from("a,b,c;31,8;;;half,yes")
+			.is_err()
+		);
+	}
+	#[test]
+	fn person_display_round_trip() {
+		for line in [
+			"a,b,c;1,1,1;2,2;3,3",
+			" a ,, ;;; ",
+			"a,b,;1,1;;;note, flowers, chocolate",
+		] {
+			let person = Person::try_from(line).unwrap();
+			let text = person.to_string();
+			let (kind, value) = text.split_once('=').unwrap();
+			assert_eq!(
+				kind.trim(),
+				"person"
+			);
+			assert_eq!(
+				Person::try_from(value).unwrap(),
+				person
+			);
+		}
+		assert_eq!(
+			Person::try_from("a,b,c;1,1,1;2,2;").unwrap().to_string(),
+			"person = a, b, c ; 01,01,0001 ; 02,02 ;"
 		);
 	}
 	#[test]
@@ -0,0 +1,52 @@
+use super::*;
+
+// first_name;day,month, one per line
+const TABLE: &str = include_str!("table.txt");
+
+// return the bundled (first_name, saint_day) pairs
+pub fn table() -> impl Iterator<Item = (&'static str, date::Recurring)> {
+	TABLE.lines().filter_map(|line| {
+		let (name, saint_day) = line.split_once(';')?;
+		let saint_day = date::Recurring::try_from(saint_day).ok()?;
+		Some((name.trim(), saint_day))
+	})
+}
+
+// return the saint day of <first_name>, ignoring case
+pub fn lookup(first_name: &str) -> Option<date::Recurring> {
+	let first_name = first_name.trim().to_lowercase();
+	table()
+		.find(|(name, _)| name.to_lowercase() == first_name)
+		.map(|(_, saint_day)| saint_day)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn table_parse() {
+		assert_eq!(
+			table().count(),
+			TABLE.lines().count()
+		);
+	}
+	#[test]
+	fn lookup_present() {
+		assert_eq!(
+			lookup("Nicolas"),
+			Some(date::Recurring::new(6,12))
+		);
+		assert_eq!(
+			lookup("  étienne "),
+			Some(date::Recurring::new(26,12))
+		);
+	}
+	#[test]
+	fn lookup_absent() {
+		assert_eq!(
+			lookup("Rustacean"),
+			None
+		);
+	}
+} // mod test
@@ -4,33 +4,105 @@ use super::*;
 pub struct Special {
 	desc: String,
 	date: date::Fixed,
+	// the event comes back every N years from date, None for one-off events
+	every: Option<u32>,
+	// see Event::lead
+	lead: Option<u32>,
+	// see Event::tags
+	tag: Option<String>,
 }
 
 impl TryFrom<&str> for Special {
 	type Error = Error;
 	fn try_from(value: &str) -> Result<Self> {
-		let mut iter = value.split(';');
+		Special::parse(value, DEFAULT_SEPARATOR)
+	}
+}
+
+impl Special {
+
+	// same as try_from, the slots being separated by <separator>
+	pub fn parse(value: &str, separator: char) -> Result<Self> {
+		let mut iter = split_quoted(value, separator)?.into_iter();
 		let Some(desc) = iter.next() else {
-			return Err("missing 'desc' slot");
+			return Err(Error::Parse{ what: "missing 'desc' slot" });
 		};
 		let Some(date) = iter.next() else {
-			return Err("missing 'date' slot");
-		};
-		if iter.next().is_some() {
-			return Err("extra ';' found");
+			return Err(Error::Parse{ what: "missing 'date' slot" });
 		};
-		let desc = String::from(desc.trim());
+		let mut every = None;
+		let mut lead = None;
+		let mut tag = None;
+		for slot in iter {
+			match parse_option(slot) {
+				("every", Some(value)) => {
+					let Ok(value) = value.parse::<u32>() else {
+						return Err(Error::Parse{ what: "failed to parse every" });
+					};
+					if value == 0 {
+						return Err(Error::OutOfRange{ what: "every must be at least 1" });
+					}
+					every = Some(value);
+				},
+				("every", None) => return Err(Error::Parse{ what: "missing every value" }),
+				("lead", value) => lead = Some(parse_lead(value)?),
+				("tag", Some(value)) if !value.is_empty() => tag = Some(String::from(value)),
+				("tag", _) => return Err(Error::Parse{ what: "missing tag value" }),
+				_ => return Err(Error::Parse{ what: "extra ';' found" }),
+			}
+		}
+		let desc = unquote(desc);
+		if desc.trim().is_empty() {
+			return Err(Error::Parse{ what: "empty 'desc' slot" });
+		}
+		let desc = String::from(desc);
 		let date = date::Fixed::try_from(date)?;
-		Ok(Special{ desc, date })
+		Ok(Special{ desc, date, every, lead, tag })
+	}
+}
+
+impl fmt::Display for Special {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "special = {} ; {}", quote(&self.desc), self.date.to_rce())?;
+		if let Some(every) = self.every {
+			write!(f, " ; every, {}", every)?;
+		}
+		if let Some(lead) = self.lead {
+			write!(f, " ; lead, {}", lead)?;
+		}
+		if let Some(tag) = &self.tag {
+			write!(f, " ; tag, {}", tag)?;
+		}
+		Ok(())
 	}
 }
 
 impl IntoEvents for Special {
-	fn into_events(self) -> Vec<Event> {
+	fn into_events_in(self, context: &Context) -> Vec<Event> {
+		let (date, desc, rule) = match self.every {
+			None => (self.date, self.desc, None),
+			Some(every) => {
+				// 29/02 is clamped to 28/02 in the years that are not leap
+				let rule = date::Rule::EveryYears{ since: self.date, years: every };
+				let date = rule.next_on_or_after(context.today).unwrap_or(self.date);
+				// the number of occurrences since the first one
+				// none if the years apart do not fit
+				let desc = match self.date.year_diff(date).unwrap_or(0) / i32::try_from(every).unwrap_or(i32::MAX) {
+					0 => self.desc,
+					count => format!("{} (year {})", self.desc, count),
+				};
+				(date, desc, Some(rule))
+			},
+		};
 		let event = Event {
 			kind: EventKind::Special,
-			date: self.date,
-			desc: self.desc,
+			date,
+			desc,
+			recurring: None,
+			rule,
+			note: None,
+			lead: self.lead,
+			tags: self.tag.into_iter().collect(),
 		};
 		vec![event]
 	}
@@ -40,14 +112,149 @@ impl IntoEvents for Special {
 mod parse {
 	use super::*;
 
+	fn new_special(date: date::Fixed, every: Option<u32>) -> Special {
+		Special{ desc: String::from("desc"), date, every, lead: None, tag: None }
+	}
+
 	#[test]
 	fn full() {
 		assert_eq!(
 			Special::try_from("  desc   ;1,1,1").unwrap(),
-			Special{ desc: String::from("desc"), date: date::Fixed::new(1,1,1) }
+			new_special(date::Fixed::new(1,1,1), None)
+		);
+	}
+	#[test]
+	fn every() {
+		assert_eq!(
+			Special::try_from("desc ; 1,1,2020 ; every, 2").unwrap(),
+			new_special(date::Fixed::new(1,1,2020), Some(2))
+		);
+		assert!(
+			Special::try_from("desc ; 1,1,2020 ; every, 0")
+			.is_err()
+		);
+		assert!(
+			Special::try_from("desc ; 1,1,2020 ; every")
+			.is_err()
+		);
+	}
+	#[test]
+	fn lead() {
+		let special = Special::try_from("desc ; 1,1,2020 ; lead, 5 ; every, 2").unwrap();
+		assert_eq!(
+			special,
+			Special{ lead: Some(5), ..new_special(date::Fixed::new(1,1,2020), Some(2)) }
+		);
+		assert_eq!(
+			special.to_string(),
+			"special = desc ; 01,01,2020 ; every, 2 ; lead, 5"
+		);
+		assert!(
+			Special::try_from("desc ; 1,1,2020 ; lead, soon")
+			.is_err()
+		);
+	}
+	#[test]
+	fn tag() {
+		let special = Special::try_from("desc ; 1,1,2030 ; tag, work").unwrap();
+		assert_eq!(
+			special,
+			Special{ tag: Some(String::from("work")), ..new_special(date::Fixed::new(1,1,2030), None) }
+		);
+		assert_eq!(
+			special.to_string(),
+			"special = desc ; 01,01,2030 ; tag, work"
+		);
+		assert_eq!(
+			special.into_events()[0].tags,
+			vec![String::from("work")]
+		);
+		assert!(
+			Special::try_from("desc ; 1,1,2030 ; tag")
+			.is_err()
+		);
+	}
+	#[test]
+	fn upcoming_with_lead() {
+		let in_ten_days = (0..10).fold(date::Fixed::now(), |date, _| date.next());
+		let in_three_days = (0..3).fold(date::Fixed::now(), |date, _| date.next());
+		let special = Special{ lead: Some(5), ..new_special(in_three_days, None) };
+		let events = special.into_events();
+		assert_eq!(
+			get_upcoming(&events, date::Fixed::now()).len(),
+			1
+		);
+		let special = Special{ lead: Some(5), ..new_special(in_ten_days, None) };
+		let events = special.into_events();
+		assert!(get_upcoming(&events, date::Fixed::now()).is_empty());
+	}
+	#[test]
+	fn display() {
+		assert_eq!(
+			new_special(date::Fixed::new(1,1,2030), None).to_string(),
+			"special = desc ; 01,01,2030"
+		);
+		assert_eq!(
+			new_special(date::Fixed::new(1,1,2030), Some(2)).to_string(),
+			"special = desc ; 01,01,2030 ; every, 2"
 		);
 	}
 	#[test]
+	fn roll_two_years() {
+		let base = date::Fixed::new(15,6,2020);
+		let rule = date::Rule::EveryYears{ since: base, years: 2 };
+		assert_eq!(
+			rule.next_on_or_after(date::Fixed::new(1,1,2025)),
+			Some(date::Fixed::new(15,6,2026))
+		);
+		assert_eq!(
+			rule.next_on_or_after(date::Fixed::new(1,1,2026)),
+			Some(date::Fixed::new(15,6,2026))
+		);
+		assert_eq!(
+			rule.next_on_or_after(date::Fixed::new(16,6,2026)),
+			Some(date::Fixed::new(15,6,2028))
+		);
+		assert_eq!(
+			rule.next_on_or_after(date::Fixed::new(1,1,2019)),
+			Some(base)
+		);
+		let rule = date::Rule::EveryYears{ since: base, years: 1000 };
+		assert_eq!(
+			rule.next_on_or_after(date::Fixed::new(1,1,2030)),
+			Some(date::Fixed::new(15,6,3020))
+		);
+	}
+	#[test]
+	fn into_events_every() {
+		let base = date::Fixed::new(1,1,1990);
+		let events = new_special(base, Some(2)).into_events();
+		let date = events[0].date;
+		assert!(date >= date::Fixed::now());
+		assert!(date.year() - 2 <= date::Fixed::now().year());
+		assert_eq!(
+			base.year_diff(date).unwrap() % 2,
+			0
+		);
+		assert_eq!(
+			events[0].desc,
+			format!("desc (year {})", base.year_diff(date).unwrap() / 2)
+		);
+		let context = Context{ today: date::Fixed::new(1,6,2026), ..Context::default() };
+		let events = new_special(date::Fixed::new(15,6,2020), Some(2)).into_events_in(&context);
+		assert_eq!(
+			(events[0].date, events[0].desc.as_str()),
+			(date::Fixed::new(15,6,2026), "desc (year 3)")
+		);
+		let (projected, _) = project(&events, 2030, date::LeapDayPolicy::Feb28);
+		assert_eq!(
+			projected.iter().map(|e| e.date).collect::<Vec<_>>(),
+			vec![date::Fixed::new(15,6,2030)]
+		);
+		assert!(project(&events, 2029, date::LeapDayPolicy::Feb28).0.is_empty());
+		assert!(project(&events, 2018, date::LeapDayPolicy::Feb28).0.is_empty());
+	}
+	#[test]
 	fn missing_date() {
 		assert!(
 			Special::try_from("desc")
@@ -55,6 +262,13 @@ mod parse {
 		);
 	}
 	#[test]
+	fn empty_desc() {
+		assert!(
+			Special::try_from("   ;1,1,2025")
+			.is_err()
+		);
+	}
+	#[test]
 	fn extra_slot() {
 		assert!(
 			Special::try_from("desc;1,1,1;1,1,1")
@@ -0,0 +1,160 @@
+use super::*;
+use rusqlite::Connection;
+
+// expected schema:
+//
+// CREATE TABLE events (
+// 	kind TEXT NOT NULL,   -- an EventKind name, e.g. "birthday" or "holiday"
+// 	day INTEGER NOT NULL,
+// 	month INTEGER NOT NULL,
+// 	year INTEGER,         -- NULL for events coming back every year
+// 	desc TEXT NOT NULL
+// );
+
+type Row = (String, u32, u32, Option<i32>, String);
+
+// read the events stored in the database at <path>
+// the year-less rows are moved to their next match from <context>.today, as the .rce entries
+pub fn load_from_sqlite<P: AsRef<Path>>(path: P, context: &Context) -> Result<Vec<Event>> {
+	let Ok(connection) = Connection::open(path) else {
+		return Err(Error::Io{ what: "could not open database" });
+	};
+	load_from_connection(&connection, context)
+}
+
+// read the events stored in an opened database
+pub fn load_from_connection(connection: &Connection, context: &Context) -> Result<Vec<Event>> {
+	let Ok(mut statement) = connection.prepare("SELECT kind, day, month, year, desc FROM events") else {
+		return Err(Error::Io{ what: "could not query events" });
+	};
+	let Ok(rows) = statement.query_map([], |row| {
+		Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?))
+	}) else {
+		return Err(Error::Io{ what: "could not query events" });
+	};
+	let mut events: Vec<Event> = Vec::new();
+	for row in rows {
+		let Ok(row) = row else {
+			return Err(Error::Io{ what: "could not read event row" });
+		};
+		events.push(into_event(row, context)?);
+	}
+	Ok(events)
+}
+
+fn into_event((kind, day, month, year, desc): Row, context: &Context) -> Result<Event> {
+	let kind = EventKind::try_from(kind.as_str())?;
+	let recurring = date::Recurring::try_new(day, month)?;
+	let event = match year {
+		None => Event {
+			kind,
+			date: date::Fixed::from(recurring).next_match_on(context.today, context.leap_day),
+			desc,
+			recurring: Some(recurring),
+			rule: None,
+			note: None,
+			lead: None,
+			tags: Vec::new(),
+		},
+		Some(year) => Event {
+			kind,
+			date: date::Fixed::try_new(day, month, year)?,
+			desc,
+			recurring: None,
+			rule: None,
+			note: None,
+			lead: None,
+			tags: Vec::new(),
+		},
+	};
+	Ok(event)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn new_connection() -> Connection {
+		let connection = Connection::open_in_memory().unwrap();
+		connection.execute_batch("
+			CREATE TABLE events (
+				kind TEXT NOT NULL,
+				day INTEGER NOT NULL,
+				month INTEGER NOT NULL,
+				year INTEGER,
+				desc TEXT NOT NULL
+			);
+			INSERT INTO events VALUES ('holiday', 25, 12, NULL, 'Christmas');
+			INSERT INTO events VALUES ('special', 4, 7, 2030, 'IMPORTANT');
+		").unwrap();
+		connection
+	}
+
+	#[test]
+	fn load_rows() {
+		let events = load_from_connection(&new_connection(), &Context::default()).unwrap();
+		assert_eq!(
+			events.len(),
+			2
+		);
+		assert!(events[0].kind == EventKind::Holiday);
+		assert_eq!(
+			events[0].recurring,
+			Some(date::Recurring::new(25,12))
+		);
+		assert_eq!(
+			events[0].desc,
+			"Christmas"
+		);
+		assert!(events[1].kind == EventKind::Special);
+		assert_eq!(
+			events[1].date,
+			date::Fixed::new(4,7,2030)
+		);
+	}
+	#[test]
+	fn load_leap_day_in_context() {
+		let connection = new_connection();
+		connection.execute("INSERT INTO events VALUES ('birthday', 29, 2, NULL, 'Leap')", []).unwrap();
+		let date = |leap_day| {
+			let context = Context{ today: date::Fixed::new(1,1,2023), leap_day, ..Context::default() };
+			load_from_connection(&connection, &context).unwrap()[2].date
+		};
+		assert_eq!(
+			date(date::LeapDayPolicy::Feb28),
+			date::Fixed::new(28,2,2023)
+		);
+		assert_eq!(
+			date(date::LeapDayPolicy::Mar1),
+			date::Fixed::new(1,3,2023)
+		);
+		assert_eq!(
+			date(date::LeapDayPolicy::Skip),
+			date::Fixed::new(29,2,2024)
+		);
+	}
+	#[test]
+	fn load_unknown_kind() {
+		let connection = new_connection();
+		connection.execute("INSERT INTO events VALUES ('chore', 1, 1, NULL, 'trash')", []).unwrap();
+		assert!(
+			load_from_connection(&connection, &Context::default())
+			.is_err()
+		);
+	}
+	#[test]
+	fn load_invalid_date() {
+		let connection = new_connection();
+		connection.execute("INSERT INTO events VALUES ('special', 29, 2, 2023, 'not leap')", []).unwrap();
+		assert_eq!(
+			load_from_connection(&connection, &Context::default()),
+			Err(Error::OutOfRange{ what: "day out of range for month" })
+		);
+		let connection = new_connection();
+		connection.execute("INSERT INTO events VALUES ('holiday', 1, 13, NULL, 'no month')", []).unwrap();
+		assert_eq!(
+			load_from_connection(&connection, &Context::default()),
+			Err(Error::OutOfRange{ what: "month out of range" })
+		);
+	}
+} // mod test
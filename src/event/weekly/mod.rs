@@ -0,0 +1,117 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Weekly {
+	desc: String,
+	date: date::Weekly,
+}
+
+impl TryFrom<&str> for Weekly {
+	type Error = Error;
+	fn try_from(value: &str) -> Result<Self> {
+		Weekly::parse(value, DEFAULT_SEPARATOR)
+	}
+}
+
+impl Weekly {
+
+	// same as try_from, the slots being separated by <separator>
+	pub fn parse(value: &str, separator: char) -> Result<Self> {
+		let mut iter = split_quoted(value, separator)?.into_iter();
+		let Some(desc) = iter.next() else {
+			return Err(Error::Parse{ what: "missing 'desc' slot" });
+		};
+		let Some(weekday) = iter.next() else {
+			return Err(Error::Parse{ what: "missing 'weekday' slot" });
+		};
+		if iter.next().is_some() {
+			return Err(Error::Parse{ what: "extra ';' found" });
+		};
+		let desc = unquote(desc);
+		if desc.trim().is_empty() {
+			return Err(Error::Parse{ what: "empty 'desc' slot" });
+		}
+		let desc = String::from(desc);
+		let date = date::Weekly::try_from(weekday)?;
+		Ok(Weekly{ desc, date })
+	}
+}
+
+impl fmt::Display for Weekly {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "weekly = {} ; {}", quote(&self.desc), self.date.weekday)
+	}
+}
+
+impl IntoEvents for Weekly {
+	fn into_events_in(self, context: &Context) -> Vec<Event> {
+		let event = Event {
+			kind: EventKind::Weekly,
+			date: self.date.next_on_or_after(context.today),
+			desc: self.desc,
+			recurring: None,
+			rule: None,
+			note: None,
+			lead: None,
+			tags: Vec::new(),
+		};
+		vec![event]
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn new_weekly(weekday: date::Weekday) -> Weekly {
+		Weekly{ desc: String::from("Trash day"), date: date::Weekly{ weekday } }
+	}
+
+	#[test]
+	fn parse() {
+		assert_eq!(
+			Weekly::try_from(" Trash day ; Tue ").unwrap(),
+			new_weekly(date::Weekday::Tuesday)
+		);
+		assert!(
+			Weekly::try_from("Trash day ; 1,1")
+			.is_err()
+		);
+		assert!(
+			Weekly::try_from(" ; Tue")
+			.is_err()
+		);
+		assert!(
+			Weekly::try_from("Trash day ; Tue ; Wed")
+			.is_err()
+		);
+	}
+	#[test]
+	fn display() {
+		let weekly = new_weekly(date::Weekday::Tuesday);
+		assert_eq!(
+			weekly.to_string(),
+			"weekly = Trash day ; Tuesday"
+		);
+		assert_eq!(
+			Weekly::try_from(weekly.to_string().split_once('=').unwrap().1).unwrap(),
+			weekly
+		);
+	}
+	#[test]
+	fn into_events() {
+		// 1/1/2024 is a Monday
+		let context = Context{ today: date::Fixed::new(1,1,2024), ..Context::default() };
+		let tuesday = new_weekly(date::Weekday::Tuesday).into_events_in(&context);
+		let monday = new_weekly(date::Weekday::Monday).into_events_in(&context);
+		assert!(tuesday[0].kind == EventKind::Weekly);
+		assert_eq!(
+			tuesday[0].date,
+			date::Fixed::new(2,1,2024)
+		);
+		assert_eq!(
+			monday[0].date,
+			date::Fixed::new(1,1,2024)
+		);
+	}
+} // mod test
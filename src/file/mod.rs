@@ -1,4 +1,5 @@
 use std::{
+	ffi::OsString,
 	fs::{self, File, ReadDir},
 	io::{BufRead, BufReader, Lines},
 	path::{Path, PathBuf},
@@ -15,29 +16,111 @@ pub fn default_location() -> PathBuf {
 	retval
 }
 
+// environment variable overriding the default location
+pub const DATA_VAR: &str = "RUSTMINDER_DATA";
+
+// location standing for the standard input
+pub const STDIN: &str = "-";
+
+// return the location of .rce files, by order of precedence:
+//   1. <arg>, the folder given on the command line
+//   2. <var>, the value of the RUSTMINDER_DATA environment variable, if not empty
+//   3. default_location()
+pub fn resolve_location(arg: Option<PathBuf>, var: Option<OsString>) -> PathBuf {
+	if let Some(arg) = arg {
+		return arg;
+	}
+	match var {
+		Some(var) if !var.is_empty() => PathBuf::from(var),
+		_ => default_location(),
+	}
+}
+
+// content of the file written by init()
+const EXAMPLE: &str = "\
+# this is a comment
+# mind the separators = , ;
+# spaces will be trimmed
+# some slots are optional
+
+# person = first_name, last_name, nickname ; birthday ; saint_day ; wedding_day
+person = Santa, CLAUS, St Nicholas ; 25,12 ; 06,12 ;
+# at least first_name or nickname must be provided, all other slots are optional
+# birthday and wedding day take an optional year
+
+# holiday = name ; begin ; end
+holiday = Christmas ; 25,12
+# recurring holiday, year and end are omitted
+holiday = Easter ; 09,04,2023
+# moving holiday, year must be set
+holiday = Summer ; 01,07,2023 ; 31,08,2023
+# spanning holiday, no slot is optional
+
+# special = name ; date
+special = IMPORTANT ; 04,07,2023
+
+# countdown = name ; date
+countdown = thesis ; 30,06,2031
+# always shows the days remaining, or how late it is once past
+
+# weekly = name ; weekday
+weekly = Trash day ; Tue
+# comes back every week
+";
+
+// create the folder at <path> and write an example .rce file in it
+// an existing example file is never overwritten
+pub fn init<P: AsRef<Path>>(path: P) -> Result<PathBuf> {
+	if fs::create_dir_all(&path).is_err() {
+		return Err(Error::Io{ what: "could not create data folder" });
+	}
+	let example = path.as_ref().join("example.rce");
+	if example.exists() {
+		return Err(Error::Io{ what: "example.rce already exists" });
+	}
+	if fs::write(&example, EXAMPLE).is_err() {
+		return Err(Error::Io{ what: "could not write example.rce" });
+	}
+	Ok(example)
+}
+
 pub struct RceIterator {
-	data: ReadDir,
+	// folders being read, the deepest last
+	stack: Vec<ReadDir>,
 }
 
-// return an iterator over the .rce files in <path>
+// return an iterator over the .rce files in <path> and its subfolders, at any depth
 pub fn find_rce<P: AsRef<Path>>(path: P) -> Result<RceIterator> {
 	let data = match fs::read_dir(path) {
 		Ok(data) => data,
-		Err(_) => { return Err("could not read data folder"); },
+		Err(_) => { return Err(Error::Io{ what: "could not read data folder" }); },
 	};
-	Ok(RceIterator { data })
+	Ok(RceIterator { stack: vec![data] })
+}
+
+// return the number of .rce files in <path> and its subfolders, 0 for a folder without any
+pub fn count_rce<P: AsRef<Path>>(path: P) -> Result<usize> {
+	Ok(find_rce(path)?.count())
 }
 
 impl Iterator for RceIterator {
 	type Item = PathBuf;
 	fn next(&mut self) -> Option<Self::Item> {
 		loop {
-			let Some(Ok(entry)) = self.data.next() else {
-				return None;
+			let data = self.stack.last_mut()?;
+			let Some(Ok(entry)) = data.next() else {
+				self.stack.pop();
+				continue;
 			};
 			let Ok(filetype) = entry.file_type() else {
 				continue;
 			};
+			if filetype.is_dir() {
+				if let Ok(data) = fs::read_dir(entry.path()) {
+					self.stack.push(data);
+				}
+				continue;
+			}
 			if !filetype.is_file() {
 				continue;
 			}
@@ -53,36 +136,468 @@ impl Iterator for RceIterator {
 	}
 }
 
-pub struct SanitizedLinesIterator {
-	data: Lines<BufReader<File>>,
+pub struct SanitizedLinesIterator<R: BufRead = BufReader<File>> {
+	data: Lines<R>,
+	// only a '#' starting the line (spaces aside) introduces a comment
+	comment_at_line_start_only: bool,
+	// number of lines read so far, comments and empty lines included
+	lines_read: usize,
+	// number of lines left out, i.e. holding only a comment or nothing
+	lines_skipped: usize,
+	// number of the first physical line of the last line returned
+	line_number: usize,
+	// field separator set by a "#!sep: <char>" header on the first line
+	separator: Option<char>,
 }
 
 // return an iterator over the non empty lines of <filename>
-pub fn read_lines<P: AsRef<Path>>(filename: P) -> Result<SanitizedLinesIterator> {
+pub fn read_lines<P: AsRef<Path>>(filename: P) -> Result<SanitizedLinesIterator<BufReader<File>>> {
 	let file = match File::open(filename) {
 		Ok(file) => file,
-		Err(_) => { return Err("could not read file"); },
+		Err(_) => { return Err(Error::Io{ what: "could not read file" }); },
 	};
-	let data = BufReader::new(file).lines();
-	Ok(SanitizedLinesIterator{ data })
+	Ok(sanitized_lines(BufReader::new(file)))
+}
+
+// same as read_lines, over any <reader>, e.g. the standard input
+pub fn sanitized_lines<R: BufRead>(reader: R) -> SanitizedLinesIterator<R> {
+	SanitizedLinesIterator{ data: reader.lines(), comment_at_line_start_only: false, lines_read: 0, lines_skipped: 0, line_number: 0, separator: None }
+}
+
+impl<R: BufRead> SanitizedLinesIterator<R> {
+
+	pub fn comment_at_line_start_only(mut self, enabled: bool) -> Self {
+		self.comment_at_line_start_only = enabled;
+		self
+	}
+
+	// return the number of the last line returned, starting at 1
+	// for a line continued with '\\', the number of its first physical line
+	pub fn line_number(&self) -> usize {
+		self.line_number
+	}
+
+	// return the number of physical lines read so far, comments and empty lines included
+	pub fn lines_read(&self) -> usize {
+		self.lines_read
+	}
+
+	// return the number of lines read so far holding only a comment or nothing
+	pub fn lines_skipped(&self) -> usize {
+		self.lines_skipped
+	}
+
+	// return the field separator declared by the header of the file, if any
+	// only known once the first line has been read
+	pub fn separator(&self) -> Option<char> {
+		self.separator
+	}
 }
 
-impl Iterator for SanitizedLinesIterator {
+// header declaring the field separator, e.g. "#!sep: |"
+const SEPARATOR_HEADER: &str = "#!sep:";
+
+// return the separator declared by <line> if it is a valid header
+// the characters already meaningful in an entry cannot be used
+fn parse_separator_header(line: &str) -> Option<char> {
+	let value = line.trim().strip_prefix(SEPARATOR_HEADER)?.trim();
+	let mut chars = value.chars();
+	let separator = chars.next()?;
+	if chars.next().is_some() || separator.is_alphanumeric() || ['=', ',', '"', '#', '\\'].contains(&separator) {
+		return None;
+	}
+	Some(separator)
+}
+
+// strip the comment and the trailing whitespace from <line>, return None if nothing remains
+// an escaped "\\#" never starts a comment and is kept as a literal '#'
+// neither does a '#' between double quotes, e.g. "note #1"
+fn sanitize(line: &str, comment_at_line_start_only: bool) -> Option<String> {
+	if comment_at_line_start_only && line.trim_start().starts_with('#') {
+		return None;
+	}
+	let mut sanitized_line = String::with_capacity(line.len());
+	let mut chars = line.chars().peekable();
+	let mut quoted = false;
+	while let Some(c) = chars.next() {
+		match c {
+			'\\' if chars.peek() == Some(&'#') => {
+				sanitized_line.push('#');
+				chars.next();
+			},
+			'#' if !comment_at_line_start_only && !quoted => break,
+			'"' => {
+				quoted = !quoted;
+				sanitized_line.push(c);
+			},
+			_ => sanitized_line.push(c),
+		}
+	}
+	sanitized_line.truncate(sanitized_line.trim_end().len());
+	if sanitized_line.is_empty() {
+		return None;
+	}
+	Some(sanitized_line)
+}
+
+impl<R: BufRead> Iterator for SanitizedLinesIterator<R> {
 	type Item = String;
+	// a line ending with '\\' once its comment is stripped goes on with the next one
 	fn next(&mut self) -> Option<Self::Item> {
+		let mut joined: Option<String> = None;
 		loop {
 			let Some(Ok(line)) = self.data.next() else {
-				return None;
+				return joined;
 			};
-			let mut iter = line.as_str().split('#');
-			let Some(sanitized_line) = iter.next() else {
-				//panic!("split returned empty iterator");
-				continue;
+			self.lines_read += 1;
+			if self.lines_read == 1 {
+				self.separator = parse_separator_header(&line);
+			}
+			let sanitized_line = sanitize(&line, self.comment_at_line_start_only).unwrap_or_default();
+			let (text, continued) = match sanitized_line.trim_end().strip_suffix('\\') {
+				Some(text) => (text, true),
+				None => (sanitized_line.as_str(), false),
 			};
-			if sanitized_line.is_empty() {
+			if joined.is_none() {
+				if text.is_empty() && !continued {
+					self.lines_skipped += 1;
+					continue;
+				}
+				self.line_number = self.lines_read;
+			}
+			let logical_line = joined.get_or_insert_with(String::new);
+			logical_line.push_str(text);
+			if continued {
+				continue;
+			}
+			if logical_line.is_empty() {
+				joined = None;
 				continue;
 			}
-			break Some(String::from(sanitized_line))
+			break joined
 		}
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use std::io::Cursor;
+
+	#[test]
+	fn sanitize_anywhere() {
+		assert_eq!(
+			sanitize("# comment", false),
+			None
+		);
+		assert_eq!(
+			sanitize("special = cafe #1 ; 1,1,2030", false),
+			Some(String::from("special = cafe"))
+		);
+	}
+	#[test]
+	fn sanitize_escaped_hash() {
+		assert_eq!(
+			sanitize("special = Bob's cafe \\#1 ; 1,1,2030", false),
+			Some(String::from("special = Bob's cafe #1 ; 1,1,2030"))
+		);
+		assert_eq!(
+			sanitize("special = Bob's cafe ; 1,1,2030 # best in town", false),
+			Some(String::from("special = Bob's cafe ; 1,1,2030"))
+		);
+		assert_eq!(
+			sanitize("special = Bob's cafe \\#1 ; 1,1,2030 # best #1 in town", false),
+			Some(String::from("special = Bob's cafe #1 ; 1,1,2030"))
+		);
+		assert_eq!(
+			sanitize("special = Bob's cafe \\#1 ; 1,1,2030", true),
+			Some(String::from("special = Bob's cafe #1 ; 1,1,2030"))
+		);
+		assert_eq!(
+			sanitize("\\# not a comment", true),
+			Some(String::from("# not a comment"))
+		);
+	}
+	#[test]
+	fn sanitize_quoted_hash() {
+		assert_eq!(
+			sanitize("special = \"note #1\" ; 1,1,2025 # real comment", false),
+			Some(String::from("special = \"note #1\" ; 1,1,2025"))
+		);
+		assert_eq!(
+			sanitize("special = note #1 ; 1,1,2025", false),
+			Some(String::from("special = note"))
+		);
+		assert_eq!(
+			sanitize("special = \"a\" #1 \"b\" ; 1,1,2025", false),
+			Some(String::from("special = \"a\""))
+		);
+		let mut events = Vec::new();
+		let line = sanitize("special = \"note #1\" ; 1,1,2025 # real comment", false).unwrap();
+		event::add_from(&line, &mut events).unwrap();
+		assert_eq!(
+			events[0].desc,
+			"note #1"
+		);
+	}
+	#[test]
+	fn sanitize_trailing_whitespace() {
+		assert_eq!(
+			sanitize("person = John, SMITH, ; 02,05,1970   # note", false),
+			Some(String::from("person = John, SMITH, ; 02,05,1970"))
+		);
+		assert_eq!(
+			sanitize("  holiday = Summer ; 1,7 \t", true),
+			Some(String::from("  holiday = Summer ; 1,7"))
+		);
+		assert_eq!(
+			sanitize("   # only a comment", false),
+			None
+		);
+	}
+	#[test]
+	fn sanitize_line_start_only() {
+		assert_eq!(
+			sanitize("# comment", true),
+			None
+		);
+		assert_eq!(
+			sanitize("special = cafe #1 ; 1,1,2030", true),
+			Some(String::from("special = cafe #1 ; 1,1,2030"))
+		);
+		assert_eq!(
+			sanitize(" \t # indented comment", true),
+			None
+		);
+	}
+	#[test]
+	fn sanitize_whitespace_desc() {
+		let mut events = Vec::new();
+		let line = sanitize("special =   #note;1,1,2025", false).unwrap();
+		assert!(
+			event::add_from(&line, &mut events)
+			.is_err()
+		);
+		let line = sanitize("special =   #note;1,1,2025", true).unwrap();
+		assert!(
+			event::add_from(&line, &mut events)
+			.is_ok()
+		);
+		let line = sanitize("special =   ;1,1,2025 # note", false).unwrap();
+		assert!(
+			event::add_from(&line, &mut events)
+			.is_err()
+		);
+	}
+	#[test]
+	fn lines_continued() {
+		let mut lines = sanitized_lines(Cursor::new(concat!(
+			"# people\n",
+			"person = John, SMITH, ; 02,05,1970 ; \\\n",
+			"  ; 12,06,1995\n",
+			"special = party \\ # continued despite the comment\n",
+			"; 1,1,2030\n",
+			"special = cafe ; 1,1,2030 # not continued \\\n",
+			"holiday = ski ; 1,2,2030\n",
+		)));
+		assert_eq!(
+			lines.next(),
+			Some(String::from("person = John, SMITH, ; 02,05,1970 ;   ; 12,06,1995"))
+		);
+		assert_eq!(
+			lines.line_number(),
+			2
+		);
+		assert_eq!(
+			lines.next(),
+			Some(String::from("special = party ; 1,1,2030"))
+		);
+		assert_eq!(
+			lines.line_number(),
+			4
+		);
+		assert_eq!(
+			lines.next(),
+			Some(String::from("special = cafe ; 1,1,2030"))
+		);
+		assert_eq!(
+			lines.next(),
+			Some(String::from("holiday = ski ; 1,2,2030"))
+		);
+		assert_eq!(
+			lines.next(),
+			None
+		);
+	}
+	#[test]
+	fn lines_blank_and_comments() {
+		let content = "# header\n \t \n\nspecial = a ; 1,1,2030\n   # indented comment\n\t\n";
+		for comment_at_line_start_only in [false, true] {
+			let lines: Vec<String> = sanitized_lines(Cursor::new(content))
+				.comment_at_line_start_only(comment_at_line_start_only)
+				.collect();
+			assert_eq!(
+				lines,
+				vec![String::from("special = a ; 1,1,2030")]
+			);
+		}
+	}
+	#[test]
+	fn lines_separator_header() {
+		let mut lines = sanitized_lines(Cursor::new("#!sep: |\nspecial = a | 1,1,2030\n"));
+		assert_eq!(
+			lines.next(),
+			Some(String::from("special = a | 1,1,2030"))
+		);
+		assert_eq!(
+			lines.separator(),
+			Some('|')
+		);
+		let mut lines = sanitized_lines(Cursor::new("special = a ; 1,1,2030\n#!sep: |\n"));
+		lines.next();
+		assert_eq!(
+			lines.separator(),
+			None
+		);
+		assert_eq!(
+			parse_separator_header("#!sep: ="),
+			None
+		);
+		assert_eq!(
+			parse_separator_header("#!sep: ||"),
+			None
+		);
+	}
+	#[test]
+	fn lines_comment_stripping() {
+		let content = "special = cafe \\#1 ; 1,1,2030 # best in town\nspecial = bar #2 ; 1,1,2030\n";
+		assert_eq!(
+			sanitized_lines(Cursor::new(content)).collect::<Vec<_>>(),
+			vec!["special = cafe #1 ; 1,1,2030", "special = bar"]
+		);
+		assert_eq!(
+			sanitized_lines(Cursor::new(content)).comment_at_line_start_only(true).collect::<Vec<_>>(),
+			vec!["special = cafe #1 ; 1,1,2030 # best in town", "special = bar #2 ; 1,1,2030"]
+		);
+	}
+	#[test]
+	fn lines_from_reader() {
+		let reader = Cursor::new("# header\nspecial = a ; 1,1,2030 # note\n\nholiday = b \\\n ; 1,5\n");
+		let mut lines = sanitized_lines(reader);
+		assert_eq!(
+			lines.next(),
+			Some(String::from("special = a ; 1,1,2030"))
+		);
+		assert_eq!(
+			lines.next(),
+			Some(String::from("holiday = b  ; 1,5"))
+		);
+		assert_eq!(
+			lines.line_number(),
+			4
+		);
+		assert_eq!(
+			lines.next(),
+			None
+		);
+		assert_eq!(
+			(lines.lines_read(), lines.lines_skipped()),
+			(5, 2)
+		);
+	}
+	#[test]
+	fn resolve_location_precedence() {
+		assert_eq!(
+			resolve_location(Some(PathBuf::from("arg")), Some(OsString::from("var"))),
+			PathBuf::from("arg")
+		);
+		assert_eq!(
+			resolve_location(None, Some(OsString::from("var"))),
+			PathBuf::from("var")
+		);
+		assert_eq!(
+			resolve_location(None, Some(OsString::new())),
+			default_location()
+		);
+		assert_eq!(
+			resolve_location(None, None),
+			default_location()
+		);
+	}
+	#[test]
+	fn find_rce_nested() {
+		let dir = tempfile::tempdir().unwrap();
+		let deep = dir.path().join("family").join("cousins");
+		fs::create_dir_all(&deep).unwrap();
+		fs::create_dir(dir.path().join("work")).unwrap();
+		for path in [
+			dir.path().join("top.rce"),
+			dir.path().join("work").join("work.rce"),
+			deep.join("deep.rce"),
+			deep.join("ignored.txt"),
+		] {
+			fs::write(path, "").unwrap();
+		}
+		let mut found: Vec<PathBuf> = find_rce(dir.path()).unwrap().collect();
+		found.sort();
+		let mut expected = vec![
+			deep.join("deep.rce"),
+			dir.path().join("top.rce"),
+			dir.path().join("work").join("work.rce"),
+		];
+		expected.sort();
+		assert_eq!(
+			found,
+			expected
+		);
+	}
+	#[test]
+	fn count_rce_empty() {
+		let dir = tempfile::tempdir().unwrap();
+		assert_eq!(
+			count_rce(dir.path()),
+			Ok(0)
+		);
+		fs::write(dir.path().join("notes.txt"), "").unwrap();
+		assert_eq!(
+			count_rce(dir.path()),
+			Ok(0)
+		);
+		fs::write(dir.path().join("dates.rce"), "").unwrap();
+		assert_eq!(
+			count_rce(dir.path()),
+			Ok(1)
+		);
+	}
+	#[test]
+	fn init_example_parse() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("data");
+		let example = init(&path).unwrap();
+		let mut events = Vec::new();
+		for line in read_lines(&example).unwrap() {
+			event::add_from(&line, &mut events).unwrap();
+		}
+		for kind in [event::EventKind::Countdown, event::EventKind::Weekly] {
+			assert!(events.iter().any(|e| e.kind == kind));
+		}
+		assert_eq!(
+			find_rce(&path).unwrap().collect::<Vec<_>>(),
+			vec![example]
+		);
+	}
+	#[test]
+	fn init_no_overwrite() {
+		let dir = tempfile::tempdir().unwrap();
+		let example = dir.path().join("example.rce");
+		fs::write(&example, "# mine").unwrap();
+		assert!(
+			init(dir.path())
+			.is_err()
+		);
+		assert_eq!(
+			fs::read_to_string(&example).unwrap(),
+			"# mine"
+		);
+	}
+} // mod test
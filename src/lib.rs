@@ -1,9 +1,89 @@
+use std::fmt;
+
 // common return type
-// the Error is a short description of the problem
-pub type Error = &'static str;
+// each Error holds a short description of the problem
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+	// the input does not follow the expected format
+	Parse { what: &'static str },
+	// a file, folder or database could not be accessed
+	Io { what: &'static str },
+	// the input is well formed but its value is not accepted
+	OutOfRange { what: &'static str },
+}
 pub type Result<T> = std::result::Result<T, Error>;
 
+impl Error {
+	// return the short description of the problem
+	pub fn what(self) -> &'static str {
+		match self {
+			Error::Parse { what } | Error::Io { what } | Error::OutOfRange { what } => what,
+		}
+	}
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.what())
+	}
+}
+
+impl std::error::Error for Error {}
+
+// a bare description is taken as a parse error
+impl From<&'static str> for Error {
+	fn from(what: &'static str) -> Self {
+		Error::Parse { what }
+	}
+}
+
+impl From<Error> for String {
+	fn from(error: Error) -> Self {
+		error.to_string()
+	}
+}
+
 // submodules
 pub mod file;
 pub mod date;
 pub mod event;
+pub mod cli;
+pub mod locale;
+pub mod watch;
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn error_variants() {
+		assert!(matches!(
+			date::Fixed::try_from("1,13,2030"),
+			Err(Error::OutOfRange{ .. })
+		));
+		assert!(matches!(
+			date::Fixed::try_from("1/1/2030"),
+			Err(Error::Parse{ .. })
+		));
+		assert!(matches!(
+			file::read_lines("does/not/exist.rce"),
+			Err(Error::Io{ what: "could not read file" })
+		));
+	}
+	#[test]
+	fn error_from_str() {
+		let error = Error::from("missing 'desc' slot");
+		assert_eq!(
+			error,
+			Error::Parse{ what: "missing 'desc' slot" }
+		);
+		assert_eq!(
+			error.to_string(),
+			"missing 'desc' slot"
+		);
+		assert_eq!(
+			String::from(error),
+			"missing 'desc' slot"
+		);
+	}
+} // mod test
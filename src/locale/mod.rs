@@ -0,0 +1,172 @@
+use super::*;
+
+// language of the messages printed to the user
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Locale {
+	#[default]
+	English,
+	French,
+}
+
+// human readable strings, one table per locale
+pub struct Messages {
+	pub next: &'static str,
+	pub none_found: &'static str,
+	pub today: &'static str,
+	// "{}" is replaced by the number of days
+	pub in_days: &'static str,
+	// "{}" is replaced by the description of the event
+	pub happens_today: &'static str,
+	// from Monday to Sunday
+	pub weekdays: [&'static str; 7],
+	// from January to December
+	pub months: [&'static str; 12],
+}
+
+const ENGLISH: Messages = Messages {
+	next: "next",
+	none_found: "none found",
+	today: "Today!",
+	in_days: "in {} days",
+	happens_today: "{} today",
+	weekdays: ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"],
+	months: [
+		"January", "February", "March", "April", "May", "June",
+		"July", "August", "September", "October", "November", "December",
+	],
+};
+
+const FRENCH: Messages = Messages {
+	next: "prochain",
+	none_found: "aucun",
+	today: "Aujourd'hui !",
+	in_days: "dans {} jours",
+	happens_today: "{} aujourd'hui",
+	weekdays: ["lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche"],
+	months: [
+		"janvier", "février", "mars", "avril", "mai", "juin",
+		"juillet", "août", "septembre", "octobre", "novembre", "décembre",
+	],
+};
+
+impl Locale {
+
+	// return the message table
+	pub fn messages(self) -> &'static Messages {
+		match self {
+			Locale::English => &ENGLISH,
+			Locale::French => &FRENCH,
+		}
+	}
+}
+
+impl TryFrom<&str> for Locale {
+	type Error = Error;
+	fn try_from(value: &str) -> Result<Self> {
+		match value.trim() {
+			"en" => Ok(Locale::English),
+			"fr" => Ok(Locale::French),
+			_ => Err(Error::Parse{ what: "unknown locale" }),
+		}
+	}
+}
+
+impl Messages {
+
+	// return the name of <weekday>
+	pub fn weekday(&self, weekday: date::Weekday) -> &'static str {
+		self.weekdays[weekday as usize]
+	}
+
+	// return the name of <month>, counted from 1, None if out of range
+	pub fn month(&self, month: u32) -> Option<&'static str> {
+		let index = usize::try_from(month).ok()?.checked_sub(1)?;
+		self.months.get(index).copied()
+	}
+
+	// return "in <days> days"
+	pub fn in_days(&self, days: u32) -> String {
+		self.in_days.replace("{}", days.to_string().as_str())
+	}
+
+	// return "<desc> today"
+	pub fn happens_today(&self, desc: &str) -> String {
+		self.happens_today.replace("{}", desc)
+	}
+
+	// return the line introducing the next events of <kind>, <msg> being their date & descriptions
+	// or the "none found" message if omitted
+	pub fn next_line(&self, kind: &str, msg: Option<&str>) -> String {
+		format!("{} {}: {}", self.next, kind, msg.unwrap_or(self.none_found))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn parse_locale() {
+		assert_eq!(
+			Locale::try_from(" fr ").unwrap(),
+			Locale::French
+		);
+		assert!(
+			Locale::try_from("de")
+			.is_err()
+		);
+	}
+	#[test]
+	fn none_found_line() {
+		assert_eq!(
+			Locale::English.messages().next_line("birthday", None),
+			"next birthday: none found"
+		);
+		assert_eq!(
+			Locale::French.messages().next_line("birthday", None),
+			"prochain birthday: aucun"
+		);
+	}
+	#[test]
+	fn happens_today_line() {
+		assert_eq!(
+			Locale::English.messages().next_line("holiday", Some(&Locale::English.messages().happens_today("Christmas"))),
+			"next holiday: Christmas today"
+		);
+		assert_eq!(
+			Locale::French.messages().happens_today("Noël"),
+			"Noël aujourd'hui"
+		);
+	}
+	#[test]
+	fn french_names() {
+		let christmas = date::Fixed::new(25,12,2024);
+		assert_eq!(
+			christmas.format_localized(Locale::French),
+			"mercredi 25 décembre 2024"
+		);
+		assert_eq!(
+			christmas.format_localized(Locale::English),
+			"Wednesday 25 December 2024"
+		);
+		assert_eq!(
+			Locale::French.messages().weekday(date::Fixed::new(23,12,2024).weekday()),
+			"lundi"
+		);
+		assert_eq!(
+			Locale::French.messages().month(8),
+			Some("août")
+		);
+		assert_eq!(
+			Locale::French.messages().month(13),
+			None
+		);
+	}
+	#[test]
+	fn in_days() {
+		assert_eq!(
+			Locale::French.messages().in_days(3),
+			"dans 3 jours"
+		);
+	}
+} // mod test
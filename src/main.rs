@@ -1,39 +1,484 @@
-use rustminder::{date, event, file, Result};
+use colored::*;
+use rustminder::{cli, date, event, file, locale, Result};
+use std::{path::{Path, PathBuf}, process::ExitCode};
 
-fn main() -> Result<()> {
+// exit codes, for scripts
+const EXIT_UPCOMING: u8 = 0;
+const EXIT_NOTHING: u8 = 1;
+const EXIT_ERROR: u8 = 2;
+
+fn main() -> ExitCode {
+	match run() {
+		Ok(code) => ExitCode::from(code),
+		Err(e) => {
+			eprintln!("error: {}", e);
+			ExitCode::from(EXIT_ERROR)
+		},
+	}
+}
+
+fn run() -> std::result::Result<u8, String> {
+
+	let options = cli::Options::parse(std::env::args().skip(1))?;
+
+	if let Some((a, b)) = &options.diff {
+		print_diff(a, b)?;
+		return Ok(EXIT_UPCOMING);
+	}
+
+	let location = file::resolve_location(options.data.clone(), std::env::var_os(file::DATA_VAR));
+
+	if options.init {
+		let example = file::init(&location)?;
+		println!("created \"{}\"", example.display());
+		return Ok(EXIT_UPCOMING);
+	}
+
+	if options.normalize {
+		for path in file::find_rce(location)? {
+			event::normalize_file(&path, options.comment_at_line_start)?;
+			println!("normalized \"{}\"", path.display());
+		}
+		return Ok(EXIT_UPCOMING);
+	}
+
+	if options.check {
+		return check(&options, &location);
+	}
+
+	if options.json || options.tsv || !cli::color_enabled(options.no_color, std::env::var_os(cli::NO_COLOR_VAR)) {
+		colored::control::set_override(false);
+	}
+
+	if options.watch {
+		return watch(&options, &location);
+	}
+
+	let events = load(&options, &location)?;
+
+	render(&events, &options);
+
+	match options.alert_within {
+		Some(days) if !event::any_within_on(&events, days, today(&options)) => Ok(EXIT_NOTHING),
+		_ => Ok(EXIT_UPCOMING),
+	}
+}
+
+// load the events of every .rce file in <location>, then filter them as requested
+fn load(options: &cli::Options, location: &Path) -> std::result::Result<Vec<event::Event>, String> {
 
 	let mut events: Vec<event::Event> = Vec::new();
+	let mut stats = Stats::default();
+
+	// "-" reads the entries from the standard input instead of a folder
+	let paths: Vec<PathBuf> = match location == Path::new(file::STDIN) {
+		true => {
+			let loaded = load_lines(options, file::sanitized_lines(std::io::stdin().lock()), Path::new("<stdin>"), &mut stats);
+			events = report(options, loaded)?;
+			Vec::new()
+		},
+		false => {
+			if file::count_rce(location)? == 0 {
+				eprintln!("no .rce files found in {}", location.display());
+			}
+			file::find_rce(location)?.collect()
+		},
+	};
+
+	for path in paths {
+		if !options.json && !options.tsv && !options.top {
+			println!("found file \"{}\"", path.display());
+		}
+		let loaded = match file::read_lines(&path) {
+			Ok(lines) => load_lines(options, lines, &path, &mut stats),
+			Err(error) => Err(event::ParseError{ path: path.clone(), line: None, error }),
+		};
+		events.append(&mut report(options, loaded)?);
+	}
+
+	if options.verbose {
+		stats.count_events(&events);
+		eprintln!("{}", stats);
+	}
+
+	for (_, duplicate) in event::find_duplicates(&events) {
+		eprintln!("warning: duplicate {} on {}: {}", duplicate.kind.name(), duplicate.date, duplicate.desc);
+	}
+
+	if options.force_recurring {
+		events = event::force_recurring_on(events, &context(options));
+	}
+
+	for e in event::expired_on(&events, today(options)) {
+		eprintln!("expired: {} ({})", e.desc, e.date);
+	}
+
+	events = event::between(events, options.since, options.until);
+
+	if let Some(kind) = options.kind {
+		events.retain(|e| e.kind == kind);
+	}
+
+	if let Some(tag) = &options.tag {
+		events = event::with_tag(events, tag);
+	}
+
+	if options.merge {
+		events = event::merge(events);
+	}
+
+	Ok(events)
+}
+
+// parse every line of <location> without printing the events
+// each error is reported, the exit code tells whether any line failed
+fn check(options: &cli::Options, location: &Path) -> std::result::Result<u8, String> {
+	let mut errors: Vec<event::ParseError> = Vec::new();
+	if location == Path::new(file::STDIN) {
+		let stdin = std::io::stdin().lock();
+		errors = event::load_reader_lenient(stdin, Path::new("<stdin>"), options.comment_at_line_start, &context(options)).1;
+	} else {
+		for path in file::find_rce(location)? {
+			errors.append(&mut event::load_file_lenient(&path, options.comment_at_line_start, &context(options)).1);
+		}
+	}
+	for e in &errors {
+		eprintln!("error: {}", e);
+	}
+	match errors.is_empty() {
+		true => Ok(EXIT_UPCOMING),
+		false => Ok(EXIT_ERROR),
+	}
+}
+
+// return the date the events are computed from, --date if given
+fn today(options: &cli::Options) -> date::Fixed {
+	options.today.unwrap_or_else(date::Fixed::now)
+}
+
+// return how the entries are turned into events, as requested on the command line
+fn context(options: &cli::Options) -> event::Context {
+	event::Context{ expand_spans: options.expand, leap_day: options.leap_day, today: today(options) }
+}
+
+// load the events from <lines> as requested, tallying what was read in <stats>
+// the warnings of a lenient load come along with the events
+fn load_lines<R: std::io::BufRead>(options: &cli::Options, lines: file::SanitizedLinesIterator<R>, path: &Path, stats: &mut Stats) -> std::result::Result<(Vec<event::Event>, Vec<event::ParseError>), event::ParseError> {
+	let mut lines = lines.comment_at_line_start_only(options.comment_at_line_start);
+	let context = context(options);
+	let loaded = if options.robust {
+		event::load_lines_robust(&mut lines, path, &context).map(|events| (events, Vec::new()))
+	} else if options.strict {
+		event::load_lines_located(&mut lines, path, &context).map(|events| (events, Vec::new()))
+	} else {
+		Ok(event::load_lines_lenient(&mut lines, path, &context))
+	};
+	stats.files += 1;
+	stats.lines += lines.lines_read();
+	stats.skipped += lines.lines_skipped();
+	loaded
+}
+
+// print the problems met while loading a file as requested, return its events
+// only a --strict run stops at the first error
+fn report(options: &cli::Options, loaded: std::result::Result<(Vec<event::Event>, Vec<event::ParseError>), event::ParseError>) -> std::result::Result<Vec<event::Event>, String> {
+	match loaded {
+		Ok((events, warnings)) => {
+			for e in warnings {
+				eprintln!("warning: {}", e);
+			}
+			Ok(events)
+		},
+		Err(e) if options.robust => {
+			eprintln!("skipped file {}", e);
+			Ok(Vec::new())
+		},
+		Err(e) if options.strict => Err(e.to_string()),
+		Err(e) => {
+			eprintln!("warning: {}", e);
+			Ok(Vec::new())
+		},
+	}
+}
+
+// what was read from the .rce files, see --verbose
+#[derive(Default)]
+struct Stats {
+	files: usize,
+	// physical lines, comments and empty lines included
+	lines: usize,
+	// lines holding only a comment or nothing
+	skipped: usize,
+	// number of events of each kind, as in event::KIND_LIST
+	events: [usize; event::KIND_LIST.len()],
+}
+
+impl Stats {
+
+	// tally <events> by kind
+	fn count_events(&mut self, events: &[event::Event]) {
+		for e in events {
+			if let Some(index) = event::KIND_LIST.iter().position(|kind| *kind == e.kind) {
+				self.events[index] += 1;
+			}
+		}
+	}
+}
+
+impl std::fmt::Display for Stats {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		writeln!(f, "files scanned: {}", self.files)?;
+		writeln!(f, "lines read: {}", self.lines)?;
+		writeln!(f, "comment or empty lines skipped: {}", self.skipped)?;
+		let counts: Vec<String> = event::KIND_LIST.iter().zip(self.events)
+			.map(|(kind, count)| format!("{} {}", kind.name(), count))
+			.collect();
+		write!(f, "events: {}", counts.join(", "))
+	}
+}
+
+// print the events in the requested mode
+fn render(events: &[event::Event], options: &cli::Options) {
+	let today = today(options);
+	if let Some(year) = options.year {
+		print_year(events, year, options.leap_day);
+	} else if let (Some(from), Some(to)) = (options.from, options.to) {
+		print_range(events, from, to);
+	} else if let Some(days) = options.next_days {
+		print_within(events, days, today);
+	} else if options.json {
+		print_json(events, today);
+	} else if options.tsv {
+		print_tsv(events);
+	} else if options.top {
+		print_top(events, today);
+	} else if options.histogram {
+		print_histogram(events, today.year());
+	} else if options.timeline {
+		print_timeline(events, options);
+	} else {
+		print_next(events, options);
+	}
+}
 
-	let location = file::default_location();
-	for path in file::find_rce(location)? {
-		println!("found file \"{}\"", path.display());
-		for line in file::read_lines(path)? {
-			event::add_from(&line, &mut events)?;
+// redraw the events every time a .rce file changes in <location>
+#[cfg(feature = "watch")]
+fn watch(options: &cli::Options, location: &Path) -> std::result::Result<u8, String> {
+	let mut reloader = rustminder::watch::Reloader::new(|| load(options, location));
+	let redraw = |reloader: &rustminder::watch::Reloader<_, String>| {
+		print!("\x1B[2J\x1B[H");
+		render(reloader.events(), options);
+		if let Some(e) = reloader.error() {
+			eprintln!("error: {}", e);
 		}
+	};
+	redraw(&reloader);
+	rustminder::watch::watch(location, |paths| {
+		if reloader.on_change(paths) {
+			redraw(&reloader);
+		}
+	})?;
+	Ok(EXIT_UPCOMING)
+}
+
+#[cfg(not(feature = "watch"))]
+fn watch(_options: &cli::Options, _location: &Path) -> std::result::Result<u8, String> {
+	Err(String::from("--watch requires the watch feature"))
+}
+
+// print the number of events in each month of <year>
+fn print_histogram(events: &[event::Event], year: i32) {
+	print!("{}", event::render_histogram(&event::histogram(events, year)));
+}
+
+// print every event happening in <year>, in date order
+fn print_year(events: &[event::Event], year: i32, policy: date::LeapDayPolicy) {
+	let (mut projected, skipped) = event::project(events, year, policy);
+	if let Some(warning) = event::skipped_warning(year, &skipped) {
+		eprintln!("{}", warning);
+	}
+	projected.sort_by_key(|e| e.date);
+	for e in projected {
+		println!("{} {}: {}", e.date, e.kind, e.desc);
+	}
+}
+
+// print every event happening in [from, to], in date order
+fn print_range(events: &[event::Event], from: date::Fixed, to: date::Fixed) {
+	for e in event::in_range(events, from, to) {
+		println!("{}", e);
+	}
+}
+
+// print every event happening within <days> days, in date order
+fn print_within(events: &[event::Event], days: u32, today: date::Fixed) {
+	for e in event::within_days_on(events, days, today) {
+		println!("{}", e);
+	}
+}
+
+// print every upcoming event, in date order
+fn print_timeline(events: &[event::Event], options: &cli::Options) {
+	for e in event::all_upcoming_on(events, today(options)) {
+		println!("{} {}: {}", e.date, e.kind, e.full_desc(options.notes));
+	}
+}
+
+// print the next events of each kind as JSON
+fn print_json(events: &[event::Event], today: date::Fixed) {
+	let next: Vec<&event::Event> = event::KIND_LIST.iter()
+		.flat_map(|kind| event::get_next_on(events, *kind, today))
+		.collect();
+	println!("{}", event::to_json(&next, today));
+}
+
+// print the single soonest event on a line of its own
+fn print_top(events: &[event::Event], today: date::Fixed) {
+	match event::most_imminent_on(events, today) {
+		Some(e) => println!("{}", e),
+		None => println!("nothing upcoming"),
+	}
+}
+
+// print every event as tab-separated values, in date order
+fn print_tsv(events: &[event::Event]) {
+	let mut sorted = events.to_vec();
+	sorted.sort();
+	print!("{}", event::export_tsv(&sorted));
+}
+
+// print the next events of each kind
+fn print_next(events: &[event::Event], options: &cli::Options) {
+
+	let describe = |e: &event::Event| -> String {
+		let mut desc = e.full_desc(options.notes);
+		if options.zodiac && (e.kind == event::EventKind::Birthday) {
+			let birthday = e.recurring.unwrap_or(e.date.recurring());
+			desc = format!("{} ({})", desc, date::zodiac(birthday));
+		}
+		match e.bridge_note() {
+			Some(note) if options.bridge_days => format!("{} ({})", desc, note),
+			_ => desc,
+		}
+	};
+
+	let now = today(options);
+
+	if options.year_progress {
+		print_year_progress(now);
+	}
+
+	if options.next_holiday {
+		print_next_holiday(events, options.locale.unwrap_or_default().messages(), now);
+	}
+
+	let messages = options.locale.unwrap_or_default().messages();
+
+	// the dates are only spelled out in the language asked for
+	let format_date = |date: date::Fixed| match options.locale {
+		Some(locale) => date.format_localized(locale),
+		None => date.to_string(),
+	};
+
+	for e in event::get_upcoming(events, now) {
+		let when = match e.date == now {
+			true => String::from(messages.today),
+			false => messages.in_days(now.to(e.date)),
+		};
+		println!("upcoming {}: {} ({}): {}", e.kind, format_date(e.date), when, describe(e));
 	}
 
 	for kind in event::KIND_LIST {
-		let filter = event::get_next(&events, kind);
-		let mut iter = filter.iter();
+		if options.kind.is_some_and(|only| only != kind) {
+			continue;
+		}
+		let filter = event::get_next_on(events, kind, now);
 		let mut msg: String;
-		let now = date::Fixed::now();
-		match iter.next() {
-			None => msg = String::from("none found"),
+		let line = match filter.first() {
+			None => messages.next_line(&kind.to_string(), None),
 			Some(e) => {
 				let date = e.date;
-				if date == now {
-					msg = String::from("Today!");
+				if date < now {
+					msg = format!("{} (overdue by {} days)", date, date.to(now));
+				} else if options.relative {
+					msg = date::relative_phrase(now, date);
+				} else if date == now {
+					msg = String::from(messages.today);
 				} else {
-					msg = format!("{} (in {} days)", date, now.to(date));
-				}
-				msg += format!(": {}", e.desc).as_str();
-				for e in iter {
-					msg += format!(", {}", e.desc).as_str();
+					msg = format!("{} ({})", format_date(date), messages.in_days(now.to(date)));
 				}
+				let descs: Vec<String> = filter.iter().map(|e| describe(e)).collect();
+				msg += format!(": {}", event::join_limited(&descs, options.limit)).as_str();
+				messages.next_line(&kind.to_string(), Some(&msg))
 			},
+		};
+		println!("{}", line);
+		if options.show_overdue {
+			print_overdue(events, kind, now);
+		}
+		if options.past {
+			print_previous(events, kind, now);
+		}
+		if let Some(days) = options.ago {
+			print_recent(events, kind, days, now);
 		}
-		println!("next {}: {}", kind, msg);
 	}
+}
 
+// print the events added and removed from folder <a> to folder <b>
+fn print_diff(a: &Path, b: &Path) -> Result<()> {
+	let a = event::load_events(a)?;
+	let b = event::load_events(b)?;
+	let (added, removed) = event::diff(&a, &b);
+	for e in added {
+		println!("+ {} {}: {}", e.kind, e.date, e.desc);
+	}
+	for e in removed {
+		println!("- {} {}: {}", e.kind, e.date, e.desc);
+	}
 	Ok(())
 }
+
+// print the last past events of kind, if any
+fn print_overdue(events: &[event::Event], kind: event::EventKind, now: date::Fixed) {
+	let overdue = event::get_overdue_on(events, kind, now);
+	let Some(first) = overdue.first() else {
+		return;
+	};
+	let descs: Vec<&str> = overdue.iter().map(|e| e.desc.as_str()).collect();
+	println!("overdue {}: {} ({} days ago): {}", kind, first.date, first.date.to(now), descs.join(", "));
+}
+
+// print when the events of kind last happened, if ever
+fn print_previous(events: &[event::Event], kind: event::EventKind, now: date::Fixed) {
+	let Some((date, previous)) = event::get_previous_on(events, kind, now) else {
+		return;
+	};
+	let descs: Vec<&str> = previous.iter().map(|e| e.desc.as_str()).collect();
+	println!("last {}: {} ({} days ago): {}", kind, date, date.signed_to(now), descs.join(", "));
+}
+
+// print the events of <kind> that happened within the last <days> days
+fn print_recent(events: &[event::Event], kind: event::EventKind, days: u32, now: date::Fixed) {
+	for (date, e) in event::get_recent_on(events, kind, days, now) {
+		println!("recent {}: {} ({} days ago): {}", kind, date, date.to(now), e.desc);
+	}
+}
+
+// print the soonest holiday on a line of its own
+fn print_next_holiday(events: &[event::Event], messages: &locale::Messages, now: date::Fixed) {
+	let msg = match event::next_holiday(events, now) {
+		None => messages.next_line("holiday", None),
+		Some(e) if e.date == now => messages.next_line("holiday", Some(&messages.happens_today(&e.desc))),
+		Some(e) => messages.next_line("holiday", Some(&format!("{} {}", e.desc, messages.in_days(now.to(e.date))))),
+	};
+	println!("{}", msg.bold());
+}
+
+// print how far through the year <now> is
+fn print_year_progress(now: date::Fixed) {
+	let day = now.day_of_year();
+	let days = now.days_in_year();
+	let percent = (day * 100 + days / 2) / days;
+	println!("day {} of {}, {}%", day, days, percent);
+}
@@ -0,0 +1,137 @@
+use std::path::PathBuf;
+use super::*;
+
+// delay during which further changes are merged into a single reload
+pub const DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(200);
+
+// return true if one of the changed <paths> is a .rce file
+pub fn is_rce_change(paths: &[PathBuf]) -> bool {
+	paths.iter().any(|path| path.extension().is_some_and(|ext| ext == "rce"))
+}
+
+// reload the events on change, keeping the last good ones on error
+pub struct Reloader<F: FnMut() -> std::result::Result<Vec<event::Event>, E>, E> {
+	load: F,
+	events: Vec<event::Event>,
+	error: Option<E>,
+}
+
+impl<F: FnMut() -> std::result::Result<Vec<event::Event>, E>, E> Reloader<F, E> {
+
+	// load the events a first time
+	pub fn new(mut load: F) -> Self {
+		let (events, error) = match load() {
+			Ok(events) => (events, None),
+			Err(e) => (Vec::new(), Some(e)),
+		};
+		Reloader{ load, events, error }
+	}
+
+	// reload the events if a .rce file changed, return true if the view must be redrawn
+	pub fn on_change(&mut self, paths: &[PathBuf]) -> bool {
+		if !is_rce_change(paths) {
+			return false;
+		}
+		match (self.load)() {
+			Ok(events) => {
+				self.events = events;
+				self.error = None;
+			},
+			Err(e) => self.error = Some(e),
+		}
+		true
+	}
+
+	// return the last events loaded successfully
+	pub fn events(&self) -> &[event::Event] {
+		&self.events
+	}
+
+	// return the error of the last reload, if it failed
+	pub fn error(&self) -> Option<&E> {
+		self.error.as_ref()
+	}
+}
+
+// call <on_change> with the changed paths every time something changes in the folder at <path>
+// the changes happening within DEBOUNCE of each other are reported at once, never returns on success
+#[cfg(feature = "watch")]
+pub fn watch<P: AsRef<std::path::Path>, C: FnMut(&[PathBuf])>(path: P, mut on_change: C) -> Result<()> {
+	use notify::Watcher;
+	use std::sync::mpsc;
+	let (tx, rx) = mpsc::channel();
+	let Ok(mut watcher) = notify::recommended_watcher(tx) else {
+		return Err(Error::Io{ what: "failed to create the file watcher" });
+	};
+	if watcher.watch(path.as_ref(), notify::RecursiveMode::Recursive).is_err() {
+		return Err(Error::Io{ what: "failed to watch the data folder" });
+	}
+	let mut paths: Vec<PathBuf> = Vec::new();
+	loop {
+		let received = match paths.is_empty() {
+			true => rx.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+			false => rx.recv_timeout(DEBOUNCE),
+		};
+		match received {
+			Ok(Ok(event)) => paths.extend(event.paths),
+			Ok(Err(_)) => (),
+			Err(mpsc::RecvTimeoutError::Timeout) => {
+				on_change(&paths);
+				paths.clear();
+			},
+			Err(mpsc::RecvTimeoutError::Disconnected) => return Err(Error::Io{ what: "file watcher stopped" }),
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn special(desc: &str) -> event::Event {
+		let mut events = Vec::new();
+		event::add_from(format!("special = {} ; 1,1,2030", desc).as_str(), &mut events).unwrap();
+		events.remove(0)
+	}
+
+	#[test]
+	fn rce_change() {
+		assert!(is_rce_change(&[PathBuf::from("a.txt"), PathBuf::from("data/b.rce")]));
+		assert!(!is_rce_change(&[PathBuf::from("a.txt"), PathBuf::from("rce")]));
+	}
+	#[test]
+	fn reload_keeps_last_good_view() {
+		let mut results = vec![
+			Ok(vec![special("second")]),
+			Err::<Vec<event::Event>, Error>(Error::Parse{ what: "parse error" }),
+			Ok(vec![special("first")]),
+		];
+		let mut reloader = Reloader::new(move || results.pop().unwrap());
+		assert_eq!(
+			reloader.events()[0].desc,
+			"first"
+		);
+		// ignored, not a .rce file
+		assert!(!reloader.on_change(&[PathBuf::from("notes.txt")]));
+		// failed reload, last good view kept
+		assert!(reloader.on_change(&[PathBuf::from("data/a.rce")]));
+		assert_eq!(
+			reloader.error(),
+			Some(&Error::Parse{ what: "parse error" })
+		);
+		assert_eq!(
+			reloader.events()[0].desc,
+			"first"
+		);
+		// successful reload, error cleared
+		assert!(reloader.on_change(&[PathBuf::from("data/a.rce")]));
+		assert_eq!(
+			reloader.error(),
+			None
+		);
+		assert_eq!(
+			reloader.events()[0].desc,
+			"second"
+		);
+	}
+} // mod test
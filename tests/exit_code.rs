@@ -0,0 +1,68 @@
+use chrono::{Datelike, Utc};
+use std::{fs, path::Path, process::Command};
+
+// run the binary on <dir> and return its exit code
+fn exit_code(dir: &Path, args: &[&str]) -> i32 {
+	Command::new(env!("CARGO_BIN_EXE_rustminder"))
+		.arg(dir)
+		.args(args)
+		.output()
+		.unwrap()
+		.status
+		.code()
+		.unwrap()
+}
+
+fn dataset(content: &str) -> tempfile::TempDir {
+	let dir = tempfile::tempdir().unwrap();
+	fs::write(dir.path().join("test.rce"), content).unwrap();
+	dir
+}
+
+#[test]
+fn upcoming_within_window() {
+	let now = Utc::now();
+	let dir = dataset(&format!("holiday = today ; {},{}\n", now.day(), now.month()));
+	assert_eq!(exit_code(dir.path(), &["--alert-within", "3"]), 0);
+}
+
+#[test]
+fn reference_date() {
+	let dir = dataset("special = far away ; 1,1,9999\n");
+	assert_eq!(exit_code(dir.path(), &["--alert-within", "3", "--date", "30,12,9998"]), 0);
+	assert_eq!(exit_code(dir.path(), &["--alert-within", "3", "--date", "1,12,9998"]), 1);
+}
+
+#[test]
+fn nothing_within_window() {
+	let dir = dataset("special = far away ; 1,1,9999\n");
+	assert_eq!(exit_code(dir.path(), &["--alert-within", "3"]), 1);
+}
+
+#[test]
+fn no_window() {
+	let dir = dataset("special = far away ; 1,1,9999\n");
+	assert_eq!(exit_code(dir.path(), &[]), 0);
+}
+
+#[test]
+fn error() {
+	let dir = dataset("special = broken\n");
+	assert_eq!(exit_code(dir.path(), &["--alert-within", "3", "--strict"]), 2);
+}
+
+#[test]
+fn error_skipped() {
+	let dir = dataset("special = broken\nspecial = far away ; 1,1,9999\n");
+	assert_eq!(exit_code(dir.path(), &["--alert-within", "3"]), 1);
+}
+
+#[test]
+fn check() {
+	let dir = dataset("special = far away ; 1,1,9999\n");
+	let output = Command::new(env!("CARGO_BIN_EXE_rustminder")).arg(dir.path()).arg("--check").output().unwrap();
+	assert_eq!(output.status.code(), Some(0));
+	assert!(output.stdout.is_empty() && output.stderr.is_empty());
+	let dir = dataset("special = far away ; 1,1,9999\nspecial = broken\n");
+	assert_eq!(exit_code(dir.path(), &["--check"]), 2);
+}